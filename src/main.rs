@@ -1,40 +1,399 @@
 use iced::{
-    executor, time, Alignment, Application, Command, Element, Length, Background,
+    event, executor, keyboard, time, window, Alignment, Application, Command, Element, Length, Background,
     Settings, Subscription, Theme,
 };
-use iced::widget::{button, column, container, row, text, text_input, horizontal_space, vertical_space};
+use iced::widget::{button, checkbox, column, container, progress_bar, row, text, text_input, tooltip, horizontal_space, vertical_space, Row};
 use iced::widget::container::Appearance;
+use iced::widget::text_input as text_input_style;
+use iced::widget::button as button_style;
 use iced::widget::scrollable;
 use iced::{Color, Border};
-use sysinfo::{System, Disks, Pid};
+use sysinfo::{System, Disks, Networks, Pid, Users};
 use log::info;
 use std::fs::File;
 use std::io::Write;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command as ProcessCommand;
+use std::time::{Duration, Instant};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 
 fn main() -> iced::Result {
     env_logger::Builder::from_default_env()
         .filter_level(log::LevelFilter::Info)
         .init();
 
-    LightMon::run(Settings::default())
+    // A relaunch from "Restart now": let the old window finish closing before we
+    // read the config it saved, so two instances never touch it at once
+    if let Some(parent) = parse_wait_for_pid(std::env::args().skip(1)) {
+        wait_for_exit(parent, RELAUNCH_WAIT_TIMEOUT);
+    }
+
+    let renderer = match parse_renderer_flag(std::env::args().skip(1)) {
+        Ok(renderer) => renderer,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    // iced reads ICED_BACKEND when it creates the window and tries each listed backend
+    // in order, panicking on a name it doesn't know. --renderer wins over ICED_BACKEND,
+    // a valid ICED_BACKEND wins over Settings, and an invalid one is replaced
+    let user_backends = std::env::var("ICED_BACKEND").ok();
+    if let Some(value) = &user_backends
+        && renderer.is_none()
+        && !is_valid_iced_backend(value)
+    {
+        log::warn!("Ignoring ICED_BACKEND=\"{}\" (expected wgpu, tiny-skia or both, comma-separated)", value);
+    }
+    if renderer.is_some() || !user_backends.as_deref().is_some_and(is_valid_iced_backend) {
+        let backends = renderer.unwrap_or_else(|| load_config(&get_config_path()).renderer).backends();
+        // SAFETY: still single-threaded here, nothing else is reading the environment yet
+        unsafe { std::env::set_var("ICED_BACKEND", backends) };
+    }
+    info!("Renderer: trying {}", std::env::var("ICED_BACKEND").unwrap_or_default());
+
+    let result = LightMon::run(Settings::default());
+    if let Err(e) = &result {
+        log::error!("Couldn't start the UI ({}). Try `--renderer tiny-skia` for software rendering", e);
+    }
+    result
+}
+
+// Which graphics backend to draw with (Settings or --renderer)
+// Auto is iced's own default order: wgpu, then tiny-skia if wgpu can't start.
+// The other two force one renderer, for GPUs where wgpu starts but draws a blank window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum RendererChoice {
+    Auto,
+    Wgpu,
+    TinySkia,
+}
+
+impl RendererChoice {
+    const ALL: [RendererChoice; 3] = [RendererChoice::Auto, RendererChoice::Wgpu, RendererChoice::TinySkia];
+
+    fn label(self) -> &'static str {
+        match self {
+            RendererChoice::Auto => "Auto",
+            RendererChoice::Wgpu => "GPU (wgpu)",
+            RendererChoice::TinySkia => "Software (tiny-skia)",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(RendererChoice::Auto),
+            "wgpu" | "gpu" => Some(RendererChoice::Wgpu),
+            "tiny-skia" | "tiny_skia" | "software" => Some(RendererChoice::TinySkia),
+            _ => None,
+        }
+    }
+
+    // ICED_BACKEND value - backends are tried left to right by iced
+    fn backends(self) -> &'static str {
+        match self {
+            RendererChoice::Auto => "wgpu,tiny-skia",
+            RendererChoice::Wgpu => "wgpu",
+            RendererChoice::TinySkia => "tiny-skia",
+        }
+    }
+}
+
+// Whether iced will accept an ICED_BACKEND value without panicking
+fn is_valid_iced_backend(value: &str) -> bool {
+    value.split(',').all(|backend| matches!(backend.trim(), "wgpu" | "tiny-skia"))
+}
+
+// Pick --renderer <auto|wgpu|tiny-skia> (or --renderer=...) out of the command line.
+// Other arguments are ignored; None means the flag wasn't given
+fn parse_renderer_flag(args: impl IntoIterator<Item = String>) -> Result<Option<RendererChoice>, String> {
+    let mut args = args.into_iter();
+    let mut choice = None;
+    while let Some(arg) = args.next() {
+        let value = if arg == "--renderer" {
+            args.next().ok_or("--renderer needs a value: auto, wgpu or tiny-skia")?
+        } else if let Some(value) = arg.strip_prefix("--renderer=") {
+            value.to_string()
+        } else {
+            continue;
+        };
+        choice = Some(RendererChoice::parse(&value).ok_or_else(|| {
+            format!("Unknown renderer \"{}\" (expected auto, wgpu or tiny-skia)", value)
+        })?);
+    }
+    Ok(choice)
+}
+
+// Passed to a relaunched copy with our PID, so it starts only once we've exited
+const WAIT_FOR_PID_FLAG: &str = "--wait-for-pid";
+
+// Upper bound on that wait, in case the old process hangs while closing
+const RELAUNCH_WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Pick --wait-for-pid <pid> out of the command line
+fn parse_wait_for_pid(args: impl IntoIterator<Item = String>) -> Option<Pid> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == WAIT_FOR_PID_FLAG {
+            return args.next()?.parse::<usize>().ok().map(Pid::from);
+        }
+    }
+    None
+}
+
+// Block until `pid` is gone (or only a zombie is left), giving up after `timeout`
+fn wait_for_exit(pid: Pid, timeout: Duration) {
+    let deadline = Instant::now() + timeout;
+    let mut sys = System::new();
+    while sys.refresh_process(pid)
+        && sys.process(pid).is_some_and(|p| p.status() != sysinfo::ProcessStatus::Zombie)
+    {
+        if Instant::now() >= deadline {
+            log::warn!("Previous LightMon (PID {}) is still running, starting anyway", pid);
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+// Command line for a relaunch: same arguments, minus --renderer so the
+// renderer chosen in Settings takes effect, and minus any earlier --wait-for-pid
+fn relaunch_args(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut args = args.into_iter();
+    let mut kept = Vec::new();
+    while let Some(arg) = args.next() {
+        if arg == "--renderer" || arg == WAIT_FOR_PID_FLAG {
+            args.next();  // Its value
+        } else if !arg.starts_with("--renderer=") {
+            kept.push(arg);
+        }
+    }
+    kept
+}
+
+// Start a fresh copy of LightMon that waits for this one to exit; the caller
+// saves the config first and closes this one straight after
+fn relaunch_current_exe() -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    ProcessCommand::new(exe)
+        .args(relaunch_args(std::env::args().skip(1)))
+        .args([WAIT_FOR_PID_FLAG.to_string(), std::process::id().to_string()])
+        // main() set this for our own window; let the new process pick again
+        .env_remove("ICED_BACKEND")
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    info!("Relaunched LightMon to apply settings");
+    Ok(())
+}
+
+// Release shown in the "What's new" panel; bump together with the notes below
+const BETA_TAG: &str = "v1.0-beta";
+
+// Release notes for the "What's new" panel, newest first
+const CHANGELOG: &[(&str, &[&str])] = &[
+    (
+        "v1.0-beta",
+        &[
+            "Separate, slower refresh for the process list to save CPU",
+            "Renderer setting and --renderer flag to force software rendering for blank windows",
+            "Usage by parent process summary on the Processes screen",
+            "Multi-select processes with Ctrl-click to export or copy them",
+            "Confirmation before killing critical system processes",
+            "CPU and memory history graphs on the Overview",
+        ],
+    ),
+];
+
+// Notes to show at startup: only for a release the user hasn't dismissed yet
+fn whats_new(last_seen_version: Option<&str>) -> Option<&'static [&'static str]> {
+    if last_seen_version == Some(BETA_TAG) {
+        return None;
+    }
+    CHANGELOG.iter().find(|(version, _)| *version == BETA_TAG).map(|(_, notes)| *notes)
 }
 
 // Our app settings - gets saved to a config file
+// Missing fields fall back to their defaults so older config files still load
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 struct AppConfig {
     refresh_interval: u64,  // How often to update stats (seconds)
+    process_refresh_interval: u64, // How often to re-list processes on the Processes tab (seconds, 0 = same as refresh_interval)
     dark_mode: bool,        // Light or dark theme
+    autostart: bool,        // Launch LightMon at OS login
+    start_minimized: bool,  // Minimize the window right after launch
+    show_status_bar: bool,  // Compact CPU/mem/disk footer on every screen
+    auto_export_minutes: u64, // Export processes to a timestamped CSV every N minutes (0 = off)
+    bar_style: BarStyle,    // Glyphs used for the Overview usage bars
+    smooth_cpu: bool,       // Show an exponential moving average of CPU instead of raw samples
+    cpu_smoothing_alpha: f32, // EMA weight of the newest sample (0-1, higher = less smoothing)
+    row_click_action: RowClickAction, // What clicking a process row does
+    throttle_when_unfocused: bool, // Refresh 5x slower while the window is in the background
+    show_mem_percent: bool, // Extra "Mem %" column (share of total RAM) in the process list
+    new_process_highlight_secs: u64, // How long newly started processes stay highlighted (0 = off)
+    overview_metrics: Vec<OverviewMetric>, // Stat boxes shown on the Overview, in display order
+    overview_layout: OverviewLayout, // Stat boxes stacked or in a 2x2 grid
+    idle_threshold: f32, // Rows using less CPU% than this are dimmed (0 = off)
+    name_max_chars: usize, // Longer process names are cut with "…" in the list (0 = off)
+    stats_in_title: bool, // Show live CPU/memory in the window title (and taskbar)
+    history_len: usize, // Samples kept for the CPU/memory history graphs
+    hide_self: bool, // Leave LightMon's own process out of the process list
+    cpu_alert_threshold: f32, // Alert when CPU% reaches this (0 = off)
+    mem_alert_threshold: f32, // Alert when memory use % reaches this (0 = off)
+    notify_on_alerts: bool, // Also show alerts as desktop notifications
+    renderer: RendererChoice, // Graphics backend, read at startup (--renderer overrides it)
+    critical_processes: Vec<String>, // User additions to the built-in "ask before killing" list
+    cpu_as_cores: bool, // Show per-process CPU as cores used (1.30) instead of percent (130.0)
+    reduce_motion: Option<bool>, // Skip tab animations; unset = follow the OS setting
+    last_export_dir: Option<PathBuf>, // Folder of the last manual export; unset = current folder
+    // Release whose notes were dismissed. A config from before this field existed is an
+    // upgrade, so it loads as None (notes shown); a fresh install starts at BETA_TAG
+    #[serde(default)]
+    last_seen_version: Option<String>,
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             refresh_interval: 1,  // Update every second by default
+            process_refresh_interval: 0,  // Follow refresh_interval, like before it existed
             dark_mode: false,     // Start with light mode
+            autostart: false,     // Don't touch the OS startup list unless asked
+            start_minimized: false,
+            show_status_bar: true,
+            auto_export_minutes: 0,  // Off unless the user opts in
+            bar_style: BarStyle::Blocks,
+            smooth_cpu: false,
+            cpu_smoothing_alpha: 0.3,
+            row_click_action: RowClickAction::Select,
+            throttle_when_unfocused: false,  // Constant refresh unless asked
+            show_mem_percent: false,
+            new_process_highlight_secs: 5,
+            overview_metrics: vec![OverviewMetric::Cpu, OverviewMetric::Memory, OverviewMetric::Disk],
+            overview_layout: OverviewLayout::Stacked,
+            idle_threshold: 0.0,  // No dimming unless asked
+            name_max_chars: 40,
+            stats_in_title: false,
+            history_len: 60,  // One minute at the default refresh rate
+            hide_self: false,
+            cpu_alert_threshold: 0.0,  // Alerts are opt-in
+            mem_alert_threshold: 0.0,
+            notify_on_alerts: false,
+            renderer: RendererChoice::Auto,
+            critical_processes: Vec::new(),
+            cpu_as_cores: false,
+            reduce_motion: None,
+            last_export_dir: None,
+            last_seen_version: Some(BETA_TAG.to_string()),  // Nothing is "new" on a first run
+        }
+    }
+}
+
+// What clicking a row in the process list does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum RowClickAction {
+    Select,  // Show the details panel
+    Expand,  // Toggle a short inline summary under the row
+    CopyPid, // Copy the PID to the clipboard
+}
+
+impl RowClickAction {
+    const ALL: [RowClickAction; 3] = [RowClickAction::Select, RowClickAction::Expand, RowClickAction::CopyPid];
+
+    fn label(self) -> &'static str {
+        match self {
+            RowClickAction::Select => "Show details",
+            RowClickAction::Expand => "Expand inline",
+            RowClickAction::CopyPid => "Copy PID",
+        }
+    }
+}
+
+// How the Overview arranges its stat boxes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum OverviewLayout {
+    Stacked, // One full box per line
+    Grid,    // Two per line, so all four fit on small windows
+}
+
+impl OverviewLayout {
+    const ALL: [OverviewLayout; 2] = [OverviewLayout::Stacked, OverviewLayout::Grid];
+    const GRID_COLUMNS: usize = 2;
+
+    fn label(self) -> &'static str {
+        match self {
+            OverviewLayout::Stacked => "Stacked",
+            OverviewLayout::Grid => "Grid",
+        }
+    }
+}
+
+// A stat box on the Overview screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum OverviewMetric {
+    Cpu,
+    Memory,
+    Disk,
+    Swap,
+}
+
+impl OverviewMetric {
+    const ALL: [OverviewMetric; 4] = [OverviewMetric::Cpu, OverviewMetric::Memory, OverviewMetric::Disk, OverviewMetric::Swap];
+
+    fn label(self) -> &'static str {
+        match self {
+            OverviewMetric::Cpu => "CPU",
+            OverviewMetric::Memory => "Memory",
+            OverviewMetric::Disk => "Disk",
+            OverviewMetric::Swap => "Swap",
+        }
+    }
+}
+
+// Move `item` one place up or down in `list`; no-op at the ends or if it's missing
+fn move_item<T: PartialEq>(list: &mut [T], item: &T, up: bool) {
+    let Some(index) = list.iter().position(|x| x == item) else {
+        return;
+    };
+    if up && index > 0 {
+        list.swap(index, index - 1);
+    } else if !up && index + 1 < list.len() {
+        list.swap(index, index + 1);
+    }
+}
+
+// How the Overview usage bars are drawn
+// Some fonts render the block characters poorly, so users can pick plain glyphs instead
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum BarStyle {
+    Blocks,    // █░
+    Ascii,     // #-
+    Dots,      // ●○
+    Graphical, // Native progress bar widget, no glyphs
+}
+
+impl BarStyle {
+    const ALL: [BarStyle; 4] = [BarStyle::Blocks, BarStyle::Ascii, BarStyle::Dots, BarStyle::Graphical];
+
+    // (filled, empty) glyphs - None for the graphical bar
+    fn glyphs(self) -> Option<(&'static str, &'static str)> {
+        match self {
+            BarStyle::Blocks => Some(("█", "░")),
+            BarStyle::Ascii => Some(("#", "-")),
+            BarStyle::Dots => Some(("●", "○")),
+            BarStyle::Graphical => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            BarStyle::Blocks => "█░",
+            BarStyle::Ascii => "#-",
+            BarStyle::Dots => "●○",
+            BarStyle::Graphical => "Graphical",
         }
     }
 }
@@ -44,40 +403,250 @@ fn get_config_path() -> PathBuf {
     PathBuf::from("lightmon_config.toml")  // Config file in same directory
 }
 
-fn load_config() -> AppConfig {
-    let config_path = get_config_path();
-    if let Ok(config_str) = fs::read_to_string(&config_path) {
-        if let Ok(config) = toml::from_str(&config_str) {
-            return config;  // Return loaded config if file exists and is valid
-        }
+fn load_config(config_path: &Path) -> AppConfig {
+    if let Ok(config_str) = fs::read_to_string(config_path)
+        && let Ok(config) = toml::from_str(&config_str)
+    {
+        return config;  // Return loaded config if file exists and is valid
     }
     AppConfig::default()  // Otherwise use defaults
 }
 
-fn save_config(config: &AppConfig) -> Result<(), String> {
-    let config_path = get_config_path();
+fn save_config(config_path: &Path, config: &AppConfig) -> Result<(), String> {
     let config_str = toml::to_string(config).map_err(|e| e.to_string())?;
-    fs::write(&config_path, config_str).map_err(|e| e.to_string())?;
+    fs::write(config_path, config_str).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Register LightMon to start at OS login
+// Linux: ~/.config/autostart .desktop entry, Windows: HKCU Run key, macOS: LaunchAgent plist
+fn enable_autostart() -> Result<(), String> {
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Can't find the LightMon executable: {}", e))?;
+    write_autostart_entry(&exe)?;
+    info!("Autostart enabled");
+    Ok(())
+}
+
+// Remove the OS startup entry created by enable_autostart()
+// Already-missing entries count as success
+fn disable_autostart() -> Result<(), String> {
+    remove_autostart_entry()?;
+    info!("Autostart disabled");
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn write_autostart_entry(exe: &Path) -> Result<(), String> {
+    let path = autostart_entry_path()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Can't create {}: {}", dir.display(), e))?;
+    }
+    let entry = format!(
+        "[Desktop Entry]\nType=Application\nName=LightMon\nExec=\"{}\"\nX-GNOME-Autostart-enabled=true\n",
+        exe.display()
+    );
+    fs::write(&path, entry).map_err(|e| format!("Can't write {}: {}", path.display(), e))
+}
+
+#[cfg(target_os = "windows")]
+fn write_autostart_entry(exe: &Path) -> Result<(), String> {
+    let output = ProcessCommand::new("reg")
+        .args([
+            "add", AUTOSTART_REG_KEY, "/v", "LightMon", "/t", "REG_SZ",
+            "/d", &format!("\"{}\"", exe.display()), "/f",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run reg: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("reg add failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn write_autostart_entry(exe: &Path) -> Result<(), String> {
+    let path = autostart_entry_path()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Can't create {}: {}", dir.display(), e))?;
+    }
+    let plist = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\"><dict>\n\
+         <key>Label</key><string>com.lightmon</string>\n\
+         <key>ProgramArguments</key><array><string>{}</string></array>\n\
+         <key>RunAtLoad</key><true/>\n\
+         </dict></plist>\n",
+        exe.display()
+    );
+    fs::write(&path, plist).map_err(|e| format!("Can't write {}: {}", path.display(), e))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn write_autostart_entry(_exe: &Path) -> Result<(), String> {
+    Err("Autostart isn't supported on this platform".into())
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn remove_autostart_entry() -> Result<(), String> {
+    let path = autostart_entry_path()?;
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Can't remove {}: {}", path.display(), e)),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn remove_autostart_entry() -> Result<(), String> {
+    let output = ProcessCommand::new("reg")
+        .args(["delete", AUTOSTART_REG_KEY, "/v", "LightMon", "/f"])
+        .output()
+        .map_err(|e| format!("Failed to run reg: {}", e))?;
+    // reg delete fails when the value is already gone, which is fine
+    if !output.status.success() {
+        info!("reg delete: {}", String::from_utf8_lossy(&output.stderr));
+    }
     Ok(())
 }
 
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn remove_autostart_entry() -> Result<(), String> {
+    Err("Autostart isn't supported on this platform".into())
+}
+
+#[cfg(target_os = "windows")]
+const AUTOSTART_REG_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+
+#[cfg(target_os = "linux")]
+fn autostart_entry_path() -> Result<PathBuf, String> {
+    let config_dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".config"))
+            .ok_or("HOME is not set")?,
+    };
+    Ok(config_dir.join("autostart").join("lightmon.desktop"))
+}
+
+#[cfg(target_os = "macos")]
+fn autostart_entry_path() -> Result<PathBuf, String> {
+    let home = std::env::var_os("HOME").ok_or("HOME is not set")?;
+    Ok(PathBuf::from(home).join("Library/LaunchAgents/com.lightmon.plist"))
+}
+
 // Main app state - holds everything we need to track
 struct LightMon {
-    sys: System,           // System info library instance
-    cpu_usage: f32,        // Current CPU usage percentage
+    sys: System,           // Process list currently on screen
+    // Second System that process refreshes run on in the background; swapped with
+    // `sys` when done so the UI never waits for (or reads half of) an enumeration
+    spare_sys: Arc<Mutex<System>>,
+    // CPU, memory and swap, refreshed every tick. Kept apart from the two process
+    // Systems: refresh_cpu() moves the baseline sysinfo divides process CPU time by,
+    // so ticking a process System would inflate its process CPU%
+    stats_sys: System,
+    process_refresh_in_flight: bool, // A background process refresh hasn't come back yet
+    after_process_refresh: Vec<Message>, // Run once the current process refresh lands
+    networks: Networks,    // Network interfaces, refreshed every tick for throughput
+    last_snapshot: Option<Instant>, // When the previous tick's data was taken
+    net_rx_rate: f64,      // Bytes received per second across all interfaces
+    net_tx_rate: f64,      // Bytes sent per second across all interfaces
+    net_talkers: Vec<NetTalker>, // Processes with the most network sockets (Linux only)
+    cpu_usage: f32,        // Current CPU usage percentage (raw sample)
+    cpu_smoothed: Option<f32>, // EMA of cpu_usage, None until the first sample
     memory_used: u64,      // Memory being used right now
     memory_total: u64,     // Total available memory
     disk_used: u64,        // Disk space used (simplified)
     disk_total: u64,       // Total disk space (simplified)
+    disks: Vec<DiskInfo>,  // Per-disk usage for the Disks tab
+    disk_sort: DiskSort,   // How to order the Disks tab
+    disk_sort_descending: bool, // Reverse the disk order
     current_screen: Screen, // Which tab we're on
     dark_mode: bool,       // Current theme
     sort_by: SortBy,       // How to sort processes
     filter_text: String,   // Search filter for processes
     selected: Option<Pid>, // Currently selected process
+    selected_name: Option<String>, // Name the selected PID had when picked, to spot PID reuse
     refresh_interval: u64, // How often to update (seconds)
     refresh_interval_input: String, // User input for refresh rate
+    process_refresh_interval: u64, // Process list refresh (seconds, 0 = same as refresh_interval)
+    process_refresh_input: String, // User input for the process list refresh
     toast_message: Option<String>, // Popup messages
+    toast_shown_at: Option<Instant>, // When the current toast appeared, for expiry
     is_exporting: bool,    // Whether we're exporting CSV
+    autostart: bool,       // Registered to start at OS login
+    start_minimized: bool, // Minimize the window on launch
+    show_status_bar: bool, // Show the footer status bar
+    diagnostics_path_input: String, // Where "Dump diagnostics" writes its report
+    auto_export_minutes: u64,        // Auto-export period in minutes (0 = off)
+    auto_export_input: String,       // User input for the auto-export period
+    last_auto_export: Option<PathBuf>, // Most recent auto-export file, shown as a subtle hint
+    bar_style: BarStyle,   // Overview bar glyphs
+    window_width: f32,     // Current window width, for the narrow layout
+    window_height: f32,    // Current window height, for how many rows fit when maximized
+    list_maximized: bool,  // Process table takes the whole window
+    snapshot_before_input: String, // Older CSV for the snapshot comparison
+    snapshot_after_input: String,  // Newer CSV for the snapshot comparison
+    snapshot_diff: Option<Vec<SnapshotDiffRow>>, // Result of the last comparison
+    nice_input: String,    // New nice value typed for the selected process
+    smooth_cpu: bool,      // Display the smoothed CPU value
+    cpu_smoothing_alpha: f32, // EMA weight of the newest sample
+    cpu_alpha_input: String,  // User input for the smoothing alpha
+    settings_path_input: String, // File used by settings export/import
+    row_click_action: RowClickAction, // What clicking a process row does
+    expanded: Option<Pid>, // Row showing its inline summary (Expand click action)
+    throttle_when_unfocused: bool, // Slow down refresh in the background
+    window_focused: bool,  // Whether our window has focus
+    show_mem_percent: bool, // Show the Mem % column
+    known_pids: HashSet<Pid>, // PIDs seen on the previous tick
+    new_pids: HashMap<Pid, Instant>, // Recently appeared PIDs and when we first saw them
+    new_process_highlight_secs: u64, // Highlight duration for new processes (0 = off)
+    new_process_highlight_input: String, // User input for the highlight duration
+    overview_metrics: Vec<OverviewMetric>, // Visible Overview stat boxes, in order
+    overview_layout: OverviewLayout, // Stacked or grid stat boxes
+    idle_threshold: f32,   // CPU% below which a row counts as idle
+    idle_threshold_input: String, // User input for the idle threshold
+    name_max_chars: usize, // Process name length limit in the list (0 = off)
+    name_max_chars_input: String, // User input for the name length limit
+    stats_in_title: bool,  // Live stats in the window title
+    cpu_history: VecDeque<f32>, // Recent CPU% samples, oldest first, at most history_len
+    mem_history: VecDeque<f32>, // Recent memory% samples, same bound
+    history_len: usize,    // Cap for the history deques
+    history_len_input: String, // User input for the history length
+    selected_history_pid: Option<Pid>, // Process the per-process CPU history belongs to
+    selected_cpu_history: VecDeque<f32>, // Selected process's recent CPU%, oldest first
+    selected_exited: bool, // Selected process is gone; its history is frozen
+    settings_search: String, // Filter for the Settings sections
+    self_pid: Option<Pid>, // Our own PID, never killed and optionally hidden
+    hide_self: bool,       // Hide our own process from the list
+    cpu_alert_threshold: f32, // CPU% that triggers an alert (0 = off)
+    cpu_alert_input: String,  // User input for the CPU alert threshold
+    mem_alert_threshold: f32, // Memory % that triggers an alert (0 = off)
+    mem_alert_input: String,  // User input for the memory alert threshold
+    notify_on_alerts: bool,   // Send alerts to the desktop too
+    cpu_alert_active: bool,   // CPU is over its threshold and was already alerted
+    mem_alert_active: bool,   // Same for memory
+    renderer: RendererChoice, // Saved renderer choice
+    startup_renderer: RendererChoice, // Saved renderer choice when this process started
+    config_path: PathBuf,  // Where settings changes are saved
+    multi_selected: HashSet<Pid>, // Ctrl-clicked rows, for batch export/copy
+    modifiers: keyboard::Modifiers, // Keyboard modifiers currently held (for Ctrl-click)
+    critical_processes: Vec<String>, // User-added critical process names
+    critical_name_input: String, // Name being typed into the safeguard list
+    pending_critical_kill: Option<Pid>, // Critical process waiting for a second "kill" confirmation
+    cpu_as_cores: bool,    // Per-process CPU shown as cores used
+    reduce_motion: Option<bool>, // User's reduce-motion choice, None = follow the OS
+    os_reduce_motion: bool, // The OS asked for reduced motion (read once at startup)
+    transition_started: Option<Instant>, // Tab switch animation in progress since
+    last_export_dir: Option<PathBuf>, // Remembered export folder
+    export_dir_input: String, // Folder manual exports are written to (blank = current folder)
+    show_parent_groups: bool, // "Usage by parent process" summary expanded
+    expanded_groups: HashSet<Pid>, // Parent groups showing their child list
+    selected_info: Option<(Pid, SelectedProcessInfo)>, // Last OS details read, and for which process
+    last_seen_version: Option<String>, // Release whose "What's new" notes were dismissed
 }
 
 // Different tabs in our app
@@ -85,220 +654,978 @@ struct LightMon {
 enum Screen {
     Overview,   // System stats overview
     Processes,  // Process list and management  
+    Disks,      // Per-disk usage
     Settings,   // App settings
 }
 
+// One process as exported to CSV: PID, name, CPU%, memory, status
+type ProcessRow = (Pid, String, f32, u64, String);
+
+// How a process differs between two exported snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffKind {
+    Appeared,    // Only in the "after" CSV
+    Disappeared, // Only in the "before" CSV
+    Changed,     // In both, with a big CPU or memory swing
+}
+
+impl DiffKind {
+    fn label(self) -> &'static str {
+        match self {
+            DiffKind::Appeared => "+ appeared",
+            DiffKind::Disappeared => "- gone",
+            DiffKind::Changed => "~ changed",
+        }
+    }
+}
+
+// One row of a snapshot comparison; before/after values are None when the
+// process is missing from that side
+#[derive(Debug, Clone)]
+struct SnapshotDiffRow {
+    kind: DiffKind,
+    pid: Pid,
+    name: String,
+    cpu: (Option<f32>, Option<f32>),
+    memory: (Option<u64>, Option<u64>),
+}
+
+// A parent process with its direct children and their combined usage
+#[derive(Debug, Clone, PartialEq)]
+struct ParentGroup {
+    parent: Pid,
+    children: Vec<Pid>,
+    cpu: f32,    // Parent + children
+    memory: u64, // Parent + children, bytes
+}
+
+// One mounted volume, as shown on the Disks tab
+#[derive(Debug, Clone)]
+struct DiskInfo {
+    name: String,        // Device name, e.g. /dev/sda1 or C:
+    mount_point: String, // Where it's mounted
+    used: u64,           // Bytes used
+    total: u64,          // Bytes total
+}
+
+// Network activity attributed to one process
+#[derive(Debug, Clone)]
+struct NetTalker {
+    pid: Pid,
+    connections: usize, // Open TCP/UDP sockets (listening sockets excluded)
+    queued_bytes: u64,  // Bytes currently sitting in send + receive queues
+}
+
+// How to order the Disks tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiskSort {
+    MountPoint, // Alphabetical, stable between refreshes
+    Usage,      // Percentage used
+}
+
 // How to sort the process list
 #[derive(Debug, Clone, Copy)]
 enum SortBy {
     Cpu,    // Sort by CPU usage
     Memory, // Sort by memory usage
+    MemoryPercent, // Sort by share of total RAM (same order as Memory, shown when that column is on)
 }
 
 // All the different things that can happen in our app
 #[derive(Debug, Clone)]
 enum Message {
     Tick,  // Timer tick - update system info
-    SystemData(f32, u64, u64, u64, u64), // New system data received
+    SystemData(f32, u64, u64, Vec<DiskInfo>), // New system data received (cpu, mem used, mem total, disks)
     GoToOverview,    // Switch to overview tab
     GoToProcesses,   // Switch to processes tab  
+    GoToDisks,       // Switch to disks tab
     GoToSettings,    // Switch to settings tab
     ToggleTheme,     // Switch between light/dark mode
     SortByCpu,       // Sort processes by CPU
     SortByMemory,    // Sort processes by memory
+    SortByMemoryPercent, // Sort processes by share of total RAM
+    ToggleMemPercentColumn(bool), // Mem % column on/off
+    SetNewProcessHighlight(String), // User changed the new-process highlight duration
+    ToggleOverviewMetric(OverviewMetric, bool), // Show/hide a stat box on the Overview
+    SetIdleThreshold(String), // User changed the idle-dimming threshold
+    SetNameMaxChars(String),  // User changed the process name length limit
+    ToggleStatsInTitle(bool), // Live stats in the window title on/off
+    SetHistoryLen(String),    // User changed how many history samples to keep
+    SettingsSearchChanged(String), // User typed in the Settings search box
+    ToggleHideSelf(bool),     // Hide LightMon's own process on/off
+    SetCpuAlertThreshold(String), // User changed the CPU alert threshold
+    SetMemAlertThreshold(String), // User changed the memory alert threshold
+    ToggleNotifyOnAlerts(bool),   // Desktop notifications for alerts on/off
+    NotificationSent(Result<(), String>), // Desktop notifier finished
+    SetRenderer(RendererChoice),  // User picked a graphics backend (applies on restart)
+    RestartNow,                   // Relaunch to apply restart-only settings
+    CountdownTick,            // Once a second on the Overview, just to redraw the countdown
+    MoveOverviewMetricUp(OverviewMetric),   // Show a stat box earlier
+    MoveOverviewMetricDown(OverviewMetric), // Show a stat box later
+    SortDisks(DiskSort),   // Order the Disks tab by name or usage
+    ToggleDiskSortOrder,   // Flip ascending/descending on the Disks tab
     FilterChanged(String), // User typed in search box
-    SelectProcess(Pid),    // User clicked a process
+    SelectProcess(Pid),    // Select a process and show its details
+    ProcessRowClicked(Pid), // User clicked a process row - runs the configured action
+    ToggleSelect(Pid),      // Ctrl-click - add/remove a row from the multi-selection
+    ClearMultiSelection,    // Drop the whole multi-selection
+    CopyMultiSelection,     // Copy the multi-selected processes to the clipboard
+    ModifiersChanged(keyboard::Modifiers), // Ctrl/Shift/... pressed or released
+    CancelKill,                    // Back out of killing a critical process
+    ToggleCpuAsCores(bool),        // Per-process CPU as cores vs percent
+    ToggleReduceMotion(bool),      // Tab animations off/on
+    AnimationFrame(Instant),       // A frame was drawn while a tab transition runs
+    CriticalNameInputChanged(String), // User typed a process name for the safeguard list
+    AddCriticalProcess,            // Add the typed name to the safeguard list
+    RemoveCriticalProcess(String), // Drop a user-added name from the safeguard list
+    SetRowClickAction(RowClickAction), // User picked a different row click action
+    SetOverviewLayout(OverviewLayout), // User switched between stacked and grid stat boxes
     SetRefreshInterval(String), // User changed refresh rate
+    SetProcessRefreshInterval(String), // User changed the process list refresh
+    ProcessTick,                // Timer tick - re-list processes (Processes tab only)
+    ProcessesRefreshed,         // Background process refresh finished
+    WriteAutoExport,            // Auto-export, once the process list is fresh
+    WriteDiagnostics(PathBuf),  // Diagnostics report, once the process list is fresh
     ExportProcesses,       // Export process list to CSV
-    ExportComplete(Result<(), String>), // CSV export finished
-    ClearToast,      // Clear popup message
+    ExportComplete(Result<PathBuf, String>), // CSV export finished
+    ExportDirChanged(String),      // User typed an export folder
+    ToggleParentGroups,            // Expand/collapse the by-parent summary
+    ToggleParentGroup(Pid),        // Show/hide one group's children
+    DismissWhatsNew,               // Hide the release notes until the next version
+    SelectedInfo(Pid, SelectedProcessInfo), // Priority/handles/shared memory read for a process
+    ToastTick(Instant), // Check whether the popup message has been up long enough
     KillProcess,     // Kill the selected process
+    ToggleAutostart(bool),      // Start at login on/off
+    ToggleStartMinimized(bool), // Start minimized on/off
+    ToggleStatusBar(bool),      // Footer status bar on/off
+    DiagnosticsPathChanged(String), // User edited the diagnostics report path
+    DumpDiagnostics,                // Write a full system report for bug reports
+    DiagnosticsComplete(Result<PathBuf, String>), // Diagnostics report finished
+    SetAutoExportInterval(String), // User changed the auto-export period
+    AutoExportTick,                // Auto-export timer fired
+    AutoExportComplete(Result<PathBuf, String>), // Auto-export finished
+    SetBarStyle(BarStyle),         // User picked different bar glyphs
+    WindowResized(f32, f32),       // Window size changed (width, height)
+    ToggleMaximizeList,            // Process table full-window on/off
+    ExitMaximizedList,             // Escape - back to the normal layout
+    SnapshotBeforeChanged(String), // User typed the older CSV path
+    SnapshotAfterChanged(String),  // User typed the newer CSV path
+    CompareSnapshots(PathBuf, PathBuf), // Diff two exported CSVs (before, after)
+    SnapshotsCompared(Result<Vec<SnapshotDiffRow>, String>), // Comparison finished
+    NetTalkers(Vec<NetTalker>),    // Per-process network usage collected
+    WindowFocusChanged(bool),      // Window gained (true) or lost (false) focus
+    ToggleThrottleUnfocused(bool), // Background throttling on/off
+    ToggleCpuSmoothing(bool),      // CPU smoothing on/off
+    SetCpuSmoothingAlpha(String),  // User changed the smoothing alpha
+    CopyProcessDetails,            // Copy the selected process's details to the clipboard
+    NiceInputChanged(String),      // User typed a new nice value
+    ReniceProcess,                 // Apply the typed nice value to the selected process
+    ExportProcessDetails,          // Write the selected process's details to a text file
+    ProcessDetailsExported(Result<PathBuf, String>), // Details file finished
+    SettingsPathChanged(String),   // User edited the settings backup path
+    ExportSettings,                // Write the current config to the backup path
+    ImportSettings,                // Read and apply a config from the backup path
+    SettingsExported(Result<PathBuf, String>), // Settings export finished
+    SettingsImported(Result<AppConfig, String>), // Settings file read and validated
+    SystemInformation(iced::system::Information), // Renderer/adapter details from iced
 }
 
-// Make our app work with the Iced framework
-impl Application for LightMon {
-    type Executor = executor::Default;
-    type Message = Message;
-    type Theme = Theme;
-    type Flags = ();
-
-    fn new(_flags: ()) -> (Self, Command<Message>) {
+impl LightMon {
+    // Build the app from an already loaded config; settings changes are saved to config_path
+    fn with_config(config: AppConfig, config_path: PathBuf) -> (Self, Command<Message>) {
         let mut sys = System::new_all();
         sys.refresh_all();  // Load initial system data
-        
-        let config = load_config();  // Load saved settings
+        let mut stats_sys = System::new();
+        stats_sys.refresh_cpu();
+        stats_sys.refresh_memory();
+
+
+        // Honor "start minimized" right after the window is created
+        let minimize = if config.start_minimized {
+            window::minimize(window::Id::MAIN, true)
+        } else {
+            Command::none()
+        };
+        // Only to log which backend iced actually ended up with
+        let startup = Command::batch([minimize, iced::system::fetch_information(Message::SystemInformation)]);
         
         (
             Self {
                 sys,
+                spare_sys: Arc::new(Mutex::new(System::new_all())),
+                stats_sys,
+                process_refresh_in_flight: false,
+                after_process_refresh: Vec::new(),
+                networks: Networks::new_with_refreshed_list(),
+                // Networks were just refreshed, so the first tick measures from startup.
+                // Also anchors the Overview's "next refresh" countdown
+                last_snapshot: Some(Instant::now()),
+                net_rx_rate: 0.0,
+                net_tx_rate: 0.0,
+                net_talkers: Vec::new(),
                 cpu_usage: 0.0,
+                cpu_smoothed: None,
                 memory_used: 0,
                 memory_total: 0,
                 disk_used: 0,
                 disk_total: 0,
+                disks: Vec::new(),
+                disk_sort: DiskSort::MountPoint,
+                disk_sort_descending: false,
                 current_screen: Screen::Overview,
                 dark_mode: config.dark_mode,
                 sort_by: SortBy::Cpu,
                 filter_text: String::new(),
                 selected: None,
+                selected_name: None,
                 refresh_interval: config.refresh_interval,
                 refresh_interval_input: config.refresh_interval.to_string(),
+                process_refresh_interval: config.process_refresh_interval,
+                process_refresh_input: config.process_refresh_interval.to_string(),
                 toast_message: None,
+                toast_shown_at: None,
                 is_exporting: false,
+                autostart: config.autostart,
+                start_minimized: config.start_minimized,
+                show_status_bar: config.show_status_bar,
+                diagnostics_path_input: "lightmon_diagnostics.txt".into(),
+                auto_export_minutes: config.auto_export_minutes,
+                auto_export_input: config.auto_export_minutes.to_string(),
+                last_auto_export: None,
+                bar_style: config.bar_style,
+                window_width: window::Settings::default().size.width,
+                window_height: window::Settings::default().size.height,
+                list_maximized: false,
+                snapshot_before_input: String::new(),
+                snapshot_after_input: String::new(),
+                snapshot_diff: None,
+                nice_input: String::new(),
+                smooth_cpu: config.smooth_cpu,
+                cpu_smoothing_alpha: config.cpu_smoothing_alpha,
+                cpu_alpha_input: config.cpu_smoothing_alpha.to_string(),
+                settings_path_input: "lightmon_settings.toml".into(),
+                row_click_action: config.row_click_action,
+                expanded: None,
+                throttle_when_unfocused: config.throttle_when_unfocused,
+                window_focused: true,
+                show_mem_percent: config.show_mem_percent,
+                known_pids: HashSet::new(),
+                new_pids: HashMap::new(),
+                new_process_highlight_secs: config.new_process_highlight_secs,
+                new_process_highlight_input: config.new_process_highlight_secs.to_string(),
+                overview_metrics: config.overview_metrics,
+                overview_layout: config.overview_layout,
+                idle_threshold: config.idle_threshold,
+                idle_threshold_input: config.idle_threshold.to_string(),
+                name_max_chars: config.name_max_chars,
+                name_max_chars_input: config.name_max_chars.to_string(),
+                stats_in_title: config.stats_in_title,
+                cpu_history: VecDeque::with_capacity(config.history_len),
+                mem_history: VecDeque::with_capacity(config.history_len),
+                history_len: config.history_len,
+                history_len_input: config.history_len.to_string(),
+                selected_history_pid: None,
+                selected_cpu_history: VecDeque::new(),
+                selected_exited: false,
+                settings_search: String::new(),
+                self_pid: sysinfo::get_current_pid().ok(),
+                hide_self: config.hide_self,
+                cpu_alert_threshold: config.cpu_alert_threshold,
+                cpu_alert_input: config.cpu_alert_threshold.to_string(),
+                mem_alert_threshold: config.mem_alert_threshold,
+                mem_alert_input: config.mem_alert_threshold.to_string(),
+                notify_on_alerts: config.notify_on_alerts,
+                cpu_alert_active: false,
+                mem_alert_active: false,
+                renderer: config.renderer,
+                startup_renderer: config.renderer,
+                multi_selected: HashSet::new(),
+                modifiers: keyboard::Modifiers::empty(),
+                critical_processes: config.critical_processes,
+                critical_name_input: String::new(),
+                pending_critical_kill: None,
+                cpu_as_cores: config.cpu_as_cores,
+                reduce_motion: config.reduce_motion,
+                os_reduce_motion: os_prefers_reduced_motion(),
+                transition_started: None,
+                export_dir_input: export_start_dir(config.last_export_dir.as_deref())
+                    .map(|dir| dir.display().to_string())
+                    .unwrap_or_default(),
+                last_export_dir: config.last_export_dir,
+                show_parent_groups: false,
+                expanded_groups: HashSet::new(),
+                selected_info: None,
+                last_seen_version: config.last_seen_version,
+                config_path,
             },
-            Command::none(),
+            startup,
         )
     }
+}
+
+// Make our app work with the Iced framework
+impl Application for LightMon {
+    type Executor = executor::Default;
+    type Message = Message;
+    type Theme = Theme;
+    type Flags = ();
+
+    fn new(_flags: ()) -> (Self, Command<Message>) {
+        let config_path = get_config_path();
+        Self::with_config(load_config(&config_path), config_path)
+    }
 
+    // Called on every redraw, so the live stats stay current without extra plumbing
     fn title(&self) -> String {
-        String::from("System Monitor")
+        if !self.stats_in_title {
+            return String::from("System Monitor");
+        }
+        format!(
+            "LightMon — CPU {:.0}% MEM {:.0}%",
+            self.displayed_cpu(),
+            usage_percent(self.memory_used, self.memory_total)
+        )
     }
 
     // Handle all the different messages/events
     fn update(&mut self, message: Message) -> Command<Message> {
         match message {
             Message::Tick => {
-                // Get fresh system data in the background
-                return Command::perform(fetch_system_data(), |(cpu, used, total, disk_used, disk_total)| {
-                    Message::SystemData(cpu, used, total, disk_used, disk_total)
+                // Refresh CPU and memory every tick (the process list has its own
+                // ProcessTick), then grab disk usage in the background
+                let (cpu, used, total) = self.refresh_system();
+                self.refresh_network_rates(Instant::now());
+                let system_data = Command::perform(fetch_disk_usage(), move |disks| {
+                    Message::SystemData(cpu, used, total, disks)
                 });
+
+                // Walking every process's sockets isn't free, so only do it while it's on screen
+                if NET_TALKERS_SUPPORTED && matches!(self.current_screen, Screen::Overview) {
+                    return Command::batch([
+                        system_data,
+                        Command::perform(fetch_net_talkers(), Message::NetTalkers),
+                    ]);
+                }
+                return system_data;
+            }
+            Message::ProcessTick => return self.refresh_processes(),
+            Message::ProcessesRefreshed => {
+                self.process_refresh_in_flight = false;
+                std::mem::swap(&mut *lock_system(&self.spare_sys), &mut self.sys);
+                self.apply_process_refresh(Instant::now());
+
+                let mut commands = vec![self.fetch_selected_info()];
+                for message in std::mem::take(&mut self.after_process_refresh) {
+                    commands.push(self.update(message));
+                }
+                return Command::batch(commands);
             }
-            Message::SystemData(cpu, used, total, disk_used, disk_total) => {
+            Message::SelectedInfo(pid, info) => {
+                // Ignore a late answer for a process that's no longer selected
+                if self.selected == Some(pid) {
+                    // Prefill the nice editor with the first reading for this selection
+                    if !matches!(self.selected_info, Some((read_pid, _)) if read_pid == pid) {
+                        self.nice_input = info.nice.map(|n| n.to_string()).unwrap_or_default();
+                    }
+                    self.selected_info = Some((pid, info));
+                }
+            }
+            Message::DismissWhatsNew => {
+                self.last_seen_version = Some(BETA_TAG.to_string());
+                self.persist_settings();
+            }
+            Message::SystemData(cpu, used, total, disks) => {
+                let (disk_used, disk_total) = disk_totals(&disks);
                 // Update our display with new system stats
                 self.cpu_usage = cpu;
+                self.cpu_smoothed = Some(match self.cpu_smoothed {
+                    Some(prev) => ema(prev, cpu, self.cpu_smoothing_alpha),
+                    None => cpu,  // Seed with the first sample
+                });
                 self.memory_used = used;
                 self.memory_total = total;
                 self.disk_used = disk_used;
                 self.disk_total = disk_total;
+                self.disks = disks;
+                let displayed_cpu = self.displayed_cpu();
+                push_bounded(&mut self.cpu_history, displayed_cpu, self.history_len);
+                push_bounded(&mut self.mem_history, usage_percent(used, total) as f32, self.history_len);
                 info!("CPU: {:.1}%, Memory: {}/{} MB", cpu, used / 1024, total / 1024);
+
+                let mut notifications = Vec::new();
+                if alert_crossed(&mut self.cpu_alert_active, displayed_cpu, self.cpu_alert_threshold) {
+                    notifications.push(self.raise_alert("CPU", displayed_cpu, self.cpu_alert_threshold));
+                }
+                let mem_percent = usage_percent(used, total) as f32;
+                if alert_crossed(&mut self.mem_alert_active, mem_percent, self.mem_alert_threshold) {
+                    notifications.push(self.raise_alert("Memory", mem_percent, self.mem_alert_threshold));
+                }
+                return Command::batch(notifications);
+            }
+            Message::GoToOverview => {
+                self.current_screen = Screen::Overview;
+                self.start_transition();
             }
-            Message::GoToOverview => self.current_screen = Screen::Overview,
             Message::GoToProcesses => {
                 self.current_screen = Screen::Processes;
-                self.sys.refresh_all();  // Refresh process list when switching to this tab
+                self.start_transition();
+                // The list isn't refreshed while the tab is hidden, so catch up now
+                return self.refresh_processes();
+            }
+            Message::GoToDisks => {
+                self.current_screen = Screen::Disks;
+                self.start_transition();
+            }
+            Message::GoToSettings => {
+                self.current_screen = Screen::Settings;
+                self.start_transition();
+            }
+            Message::AnimationFrame(now) => {
+                // Done - stop the frame subscription
+                if self.transition_started.is_some_and(|start| now.saturating_duration_since(start) >= TRANSITION_DURATION) {
+                    self.transition_started = None;
+                }
+            }
+            Message::ToggleReduceMotion(enabled) => {
+                self.reduce_motion = Some(enabled);
+                if enabled {
+                    self.transition_started = None;
+                }
+                self.persist_settings();
             }
-            Message::GoToSettings => self.current_screen = Screen::Settings,
             Message::ToggleTheme => {
                 self.dark_mode = !self.dark_mode;
                 // Auto-save the theme preference
-                let config = AppConfig {
-                    refresh_interval: self.refresh_interval,
-                    dark_mode: self.dark_mode,
-                };
-                if let Err(e) = save_config(&config) {
-                    self.toast_message = Some(format!("Couldn't save settings: {}", e));
-                }
+                self.persist_settings();
             }
             Message::SortByCpu => self.sort_by = SortBy::Cpu,
             Message::SortByMemory => self.sort_by = SortBy::Memory,
+            Message::SortByMemoryPercent => self.sort_by = SortBy::MemoryPercent,
+            Message::ToggleMemPercentColumn(enabled) => {
+                self.show_mem_percent = enabled;
+                self.persist_settings();
+            }
+            Message::SortDisks(sort) => self.disk_sort = sort,
+            Message::ToggleDiskSortOrder => self.disk_sort_descending = !self.disk_sort_descending,
             Message::FilterChanged(s) => self.filter_text = s,
-            Message::SelectProcess(pid) => self.selected = Some(pid),
+            Message::SelectProcess(pid) => {
+                self.selected = Some(pid);
+                self.selected_name = self.sys.process(pid).map(|p| p.name().to_string());
+                self.nice_input.clear();  // Filled in once the current value has been read
+                self.pending_critical_kill = None;
+                return self.fetch_selected_info();
+            }
+            Message::ProcessRowClicked(pid) if self.modifiers.control() => {
+                return self.update(Message::ToggleSelect(pid));
+            }
+            Message::ProcessRowClicked(pid) => match self.row_click_action {
+                RowClickAction::Select => return self.update(Message::SelectProcess(pid)),
+                RowClickAction::Expand => {
+                    // Clicking the expanded row again collapses it
+                    self.expanded = if self.expanded == Some(pid) { None } else { Some(pid) };
+                }
+                RowClickAction::CopyPid => {
+                    self.show_toast(format!("✅ Copied PID {}", pid));
+                    return iced::clipboard::write(pid.to_string());
+                }
+            },
+            Message::ToggleSelect(pid) => {
+                if !self.multi_selected.remove(&pid) {
+                    self.multi_selected.insert(pid);
+                }
+            }
+            Message::ClearMultiSelection => self.multi_selected.clear(),
+            Message::CopyMultiSelection => {
+                let rows = self.multi_selected_data();
+                let lines: Vec<String> = rows
+                    .iter()
+                    .map(|(pid, name, cpu, memory, status)| {
+                        format!("{}\t{}\t{:.1}%\t{}\t{}", pid, name, cpu, format_bytes(*memory), status)
+                    })
+                    .collect();
+                self.show_toast(format!("✅ Copied {} processes", rows.len()));
+                return iced::clipboard::write(lines.join("\n"));
+            }
+            Message::ModifiersChanged(modifiers) => self.modifiers = modifiers,
+            Message::SetRowClickAction(action) => {
+                self.row_click_action = action;
+                self.expanded = None;
+                self.persist_settings();
+            }
             Message::SetRefreshInterval(s) => {
                 // Let user type freely in the input field
                 self.refresh_interval_input = s.clone();
 
-                // Only update the actual refresh rate if it's a valid number
-                if let Ok(interval) = s.parse::<u64>() {
-                    self.refresh_interval = interval.max(1);  // Minimum 1 second
+                // Only update the actual refresh rate if it's a valid number in range
+                // (the input shows the error inline otherwise)
+                if let Ok(interval) = parse_in_range(&s, REFRESH_INTERVAL_RANGE) {
+                    self.refresh_interval = interval;
 
                     // Save the new setting
-                    let config = AppConfig {
-                        refresh_interval: self.refresh_interval,
-                        dark_mode: self.dark_mode,
-                    };
-                    let _ = save_config(&config);
+                    self.persist_settings();
                 }
             }
-            Message::ExportProcesses => {
-                self.is_exporting = true;  // Show "Exporting..." on button
+            Message::SetProcessRefreshInterval(s) => {
+                self.process_refresh_input = s.clone();
+
+                // 0 means "same as the update frequency"
+                if let Ok(interval) = parse_in_range(&s, PROCESS_REFRESH_RANGE) {
+                    self.process_refresh_interval = interval;
+                    self.persist_settings();
+                }
+            }
+            Message::ToggleOverviewMetric(metric, visible) => {
+                self.overview_metrics.retain(|m| *m != metric);
+                if visible {
+                    self.overview_metrics.push(metric);  // Re-shown boxes go to the end
+                }
+                self.persist_settings();
+            }
+            Message::SetOverviewLayout(layout) => {
+                self.overview_layout = layout;
+                self.persist_settings();
+            }
+            Message::MoveOverviewMetricUp(metric) | Message::MoveOverviewMetricDown(metric) => {
+                let up = matches!(message, Message::MoveOverviewMetricUp(_));
+                move_item(&mut self.overview_metrics, &metric, up);
+                self.persist_settings();
+            }
+            Message::SetNewProcessHighlight(s) => {
+                self.new_process_highlight_input = s.clone();
+
+                if let Ok(secs) = parse_in_range(&s, NEW_PROCESS_HIGHLIGHT_RANGE) {
+                    self.new_process_highlight_secs = secs;
+                    self.persist_settings();
+                }
+            }
+            Message::SetAutoExportInterval(s) => {
+                self.auto_export_input = s.clone();
+
+                // 0 turns auto-export off; invalid input is flagged inline
+                if let Ok(minutes) = parse_in_range(&s, AUTO_EXPORT_RANGE) {
+                    self.auto_export_minutes = minutes;
+                    self.persist_settings();
+                }
+            }
+            Message::AutoExportTick => {
+                // Processes are only refreshed while their tab is open; don't log a stale list
+                self.after_process_refresh.push(Message::WriteAutoExport);
+                return self.refresh_processes();
+            }
+            Message::WriteAutoExport => {
+                let path = auto_export_path(std::time::SystemTime::now());
                 let processes_data = self.get_processes_data();
-                return Command::perform(export_processes_to_csv(processes_data), Message::ExportComplete);
+                return Command::perform(
+                    async move { write_processes_csv(&path, &processes_data).map(|()| path) },
+                    Message::AutoExportComplete,
+                );
             }
-            Message::ExportComplete(result) => {
-                self.is_exporting = false;
+            Message::AutoExportComplete(result) => {
                 match result {
-                    Ok(()) => {
-                        self.toast_message = Some("✅ Processes exported to processes.csv".into());
+                    // Success is shown quietly on the Processes screen, not as a toast
+                    Ok(path) => {
+                        info!("Auto-exported processes to {}", path.display());
+                        self.last_auto_export = Some(path);
                     }
                     Err(e) => {
-                        self.toast_message = Some(format!("❌ Export failed: {}", e));
+                        self.show_toast(format!("❌ Auto-export failed: {}", e));
                     }
                 }
-                // Auto-clear the toast message after 3 seconds
-                return Command::perform(
-                    async {
-                        tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                    }, 
-                    |_| Message::ClearToast
-                );
             }
-            Message::ClearToast => {
-                self.toast_message = None;
+            Message::SetBarStyle(style) => {
+                self.bar_style = style;
+                self.persist_settings();
             }
-            Message::KillProcess => {
-                if let Some(pid) = self.selected {
-                    match self.kill_process(pid) {
-                        Ok(()) => {
-                            self.toast_message = Some(format!("✅ Killed process {}", pid));
-                            self.selected = None;  // Clear selection after killing
-                            self.sys.refresh_all();  // Refresh the process list
-                        }
-                        Err(e) => {
-                            self.toast_message = Some(format!("❌ Failed to kill process {}: {}", pid, e));
-                        }
-                    }
-                } else {
-                    self.toast_message = Some("⚠️ No process selected".into());
-                }
+            Message::WindowResized(width, height) => {
+                self.window_width = width;
+                self.window_height = height;
             }
-        }
-        Command::none()
-    }
+            Message::ToggleMaximizeList => self.list_maximized = !self.list_maximized,
+            Message::ExitMaximizedList => self.list_maximized = false,
+            Message::NetTalkers(talkers) => self.net_talkers = talkers,
+            Message::WindowFocusChanged(focused) => self.window_focused = focused,
+            Message::ToggleThrottleUnfocused(enabled) => {
+                self.throttle_when_unfocused = enabled;
+                self.persist_settings();
+            }
+            Message::ToggleCpuSmoothing(enabled) => {
+                self.smooth_cpu = enabled;
+                self.persist_settings();
+            }
+            Message::SetCpuSmoothingAlpha(s) => {
+                self.cpu_alpha_input = s.clone();
 
-    // Build the UI based on current state
-    fn view(&self) -> Element<Message> {
-        // Top navigation bar
-        let header = row![
-            text("System Monitor").size(20),
-            button("Overview").on_press(Message::GoToOverview).padding(5),
-            button("Processes").on_press(Message::GoToProcesses).padding(5),
-            horizontal_space(),
-            button("Settings").on_press(Message::GoToSettings).padding(8),
-        ]
-        .spacing(15)
-        .align_items(Alignment::Center)
-        .padding(12);
+                // 0 would freeze the display, so the range starts just above it
+                if let Ok(alpha) = parse_in_range(&s, CPU_ALPHA_RANGE) {
+                    self.cpu_smoothing_alpha = alpha;
+                    self.persist_settings();
+                }
+            }
+            Message::SettingsSearchChanged(s) => self.settings_search = s,
+            Message::CountdownTick => {}  // Nothing to update; the redraw recomputes the countdown
+            Message::SetCpuAlertThreshold(s) => {
+                self.cpu_alert_input = s.clone();
 
-        // Main content area - changes based on current tab
-        let content: Element<_> = match self.current_screen {
-            Screen::Overview => self.view_overview(),
-            Screen::Processes => self.view_processes(),
-            Screen::Settings => self.view_settings(),
-        };
+                if let Ok(threshold) = parse_in_range(&s, ALERT_THRESHOLD_RANGE) {
+                    self.cpu_alert_threshold = threshold;
+                    self.persist_settings();
+                }
+            }
+            Message::SetMemAlertThreshold(s) => {
+                self.mem_alert_input = s.clone();
 
-        let mut main = column![header, content];
+                if let Ok(threshold) = parse_in_range(&s, ALERT_THRESHOLD_RANGE) {
+                    self.mem_alert_threshold = threshold;
+                    self.persist_settings();
+                }
+            }
+            Message::SetRenderer(renderer) => {
+                self.renderer = renderer;
+                self.persist_settings();
+            }
+            Message::RestartNow => {
+                // The new process loads the config as soon as we're gone, so it must be on disk first
+                match self.save_settings().and_then(|()| relaunch_current_exe()) {
+                    Ok(()) => return window::close(window::Id::MAIN),
+                    Err(e) => self.show_toast(format!("❌ Couldn't restart LightMon: {}", e)),
+                }
+            }
+            Message::ToggleNotifyOnAlerts(enabled) => {
+                self.notify_on_alerts = enabled;
+                self.persist_settings();
+            }
+            Message::NotificationSent(result) => {
+                // Not worth a toast - the alert itself was already shown in the app
+                if let Err(e) = result {
+                    info!("Desktop notifications unavailable: {}", e);
+                }
+            }
+            Message::ToggleHideSelf(enabled) => {
+                self.hide_self = enabled;
+                self.persist_settings();
+            }
+            Message::SetHistoryLen(s) => {
+                self.history_len_input = s.clone();
+
+                if let Ok(len) = parse_in_range(&s, HISTORY_LEN_RANGE) {
+                    self.set_history_len(len);
+                    self.persist_settings();
+                }
+            }
+            Message::ToggleStatsInTitle(enabled) => {
+                self.stats_in_title = enabled;
+                self.persist_settings();
+            }
+            Message::SetIdleThreshold(s) => {
+                self.idle_threshold_input = s.clone();
+
+                if let Ok(threshold) = parse_in_range(&s, IDLE_THRESHOLD_RANGE) {
+                    self.idle_threshold = threshold;
+                    self.persist_settings();
+                }
+            }
+            Message::SetNameMaxChars(s) => {
+                self.name_max_chars_input = s.clone();
+
+                if let Ok(max) = parse_in_range(&s, NAME_MAX_CHARS_RANGE) {
+                    self.name_max_chars = max;
+                    self.persist_settings();
+                }
+            }
+            Message::NiceInputChanged(s) => self.nice_input = s,
+            Message::ReniceProcess => {
+                let Some(pid) = self.selected else {
+                    self.show_toast("⚠️ No process selected".into());
+                    return Command::none();
+                };
+                match parse_in_range(&self.nice_input, NICE_RANGE) {
+                    Err(e) => self.show_toast(format!("❌ Nice value {}", e)),
+                    Ok(nice) => match set_process_nice(pid, nice) {
+                        Ok(()) => {
+                            if let Some((read_pid, info)) = &mut self.selected_info
+                                && *read_pid == pid
+                            {
+                                info.nice = Some(nice);
+                            }
+                            self.show_toast(format!("✅ Set priority of {} to nice {}", pid, nice));
+                        }
+                        Err(e) => self.show_toast(format!("❌ Couldn't change priority of {}: {}", pid, e)),
+                    },
+                }
+            }
+            Message::CopyProcessDetails => {
+                match self.selected.and_then(|pid| self.process_details_text(pid)) {
+                    Some(details) => {
+                        self.show_toast("✅ Process details copied to clipboard".into());
+                        return iced::clipboard::write(details);
+                    }
+                    None => self.show_toast("⚠️ No process selected".into()),
+                }
+            }
+            Message::ExportProcessDetails => {
+                match self.selected.and_then(|pid| self.process_details_text(pid).map(|d| (pid, d))) {
+                    Some((pid, details)) => {
+                        let path = self.export_path(&format!("process_{}.txt", pid));
+                        return Command::perform(
+                            async move {
+                                fs::write(&path, details)
+                                    .map(|()| path)
+                                    .map_err(|e| format!("{} - check permissions", e))
+                            },
+                            Message::ProcessDetailsExported,
+                        );
+                    }
+                    None => self.show_toast("⚠️ No process selected".into()),
+                }
+            }
+            Message::ProcessDetailsExported(result) => {
+                if let Ok(path) = &result {
+                    self.remember_export_dir(path);
+                }
+                self.show_toast(match result {
+                    Ok(path) => format!("✅ Process details saved to {}", path.display()),
+                    Err(e) => format!("❌ Couldn't save process details: {}", e),
+                });
+            }
+            Message::ExportDirChanged(s) => self.export_dir_input = s,
+            Message::ToggleParentGroups => self.show_parent_groups = !self.show_parent_groups,
+            Message::ToggleParentGroup(parent) => {
+                if !self.expanded_groups.remove(&parent) {
+                    self.expanded_groups.insert(parent);
+                }
+            }
+            Message::ExportProcesses => {
+                self.is_exporting = true;  // Show "Exporting..." on button
+                // A multi-selection narrows the export to just those rows
+                let processes_data = if self.multi_selected.is_empty() {
+                    self.get_processes_data()
+                } else {
+                    self.multi_selected_data()
+                };
+                let path = self.export_path("processes.csv");
+                return Command::perform(export_processes_to_csv(path, processes_data), Message::ExportComplete);
+            }
+            Message::ExportComplete(result) => {
+                self.is_exporting = false;
+                match result {
+                    Ok(path) => {
+                        self.remember_export_dir(&path);
+                        self.show_toast(format!("✅ Processes exported to {}", path.display()));
+                    }
+                    Err(e) => {
+                        self.show_toast(format!("❌ Export failed: {}", e));
+                    }
+                }
+            }
+            Message::ToastTick(now) => self.expire_toast(now),
+            Message::KillProcess => {
+                if let Some(pid) = self.selected {
+                    // Critical processes need a second press on the confirmation prompt
+                    let critical = self.sys.process(pid).is_some_and(|p| self.is_critical_process(p.name()));
+                    if critical && self.pending_critical_kill != Some(pid) {
+                        self.pending_critical_kill = Some(pid);
+                        self.show_toast(format!("⚠️ PID {} is a critical system process - confirm below", pid));
+                        return Command::none();
+                    }
+                    self.pending_critical_kill = None;
+
+                    match self.kill_process(pid) {
+                        Ok(()) => {
+                            self.show_toast(format!("✅ Killed process {}", pid));
+                            self.selected = None;  // Clear selection after killing
+                            self.selected_name = None;
+                            return self.refresh_processes();  // Drop the killed process from the list
+                        }
+                        Err(e) => {
+                            self.show_toast(format!("❌ Failed to kill process {}: {}", pid, e));
+                        }
+                    }
+                } else {
+                    self.show_toast("⚠️ No process selected".into());
+                }
+            }
+            Message::CancelKill => self.pending_critical_kill = None,
+            Message::ToggleCpuAsCores(enabled) => {
+                self.cpu_as_cores = enabled;
+                self.persist_settings();
+            }
+            Message::CriticalNameInputChanged(s) => self.critical_name_input = s,
+            Message::AddCriticalProcess => {
+                let name = self.critical_name_input.trim().to_string();
+                if name.is_empty() || self.is_critical_process(&name) {
+                    return Command::none();  // Nothing to add, or already covered
+                }
+                self.critical_processes.push(name);
+                self.critical_name_input.clear();
+                self.persist_settings();
+            }
+            Message::RemoveCriticalProcess(name) => {
+                self.critical_processes.retain(|n| *n != name);
+                self.persist_settings();
+            }
+            Message::ToggleAutostart(enabled) => {
+                // Registering can fail on locked-down systems, so only flip the setting on success
+                let result = if enabled { enable_autostart() } else { disable_autostart() };
+                match result {
+                    Ok(()) => {
+                        self.autostart = enabled;
+                        self.show_toast(if enabled {
+                            "✅ LightMon will start at login".into()
+                        } else {
+                            "✅ LightMon will no longer start at login".into()
+                        });
+                        self.persist_settings();
+                    }
+                    Err(e) => {
+                        self.show_toast(format!("❌ Couldn't update autostart: {}", e));
+                    }
+                }
+            }
+            Message::ToggleStartMinimized(enabled) => {
+                self.start_minimized = enabled;
+                self.persist_settings();
+            }
+            Message::ToggleStatusBar(enabled) => {
+                self.show_status_bar = enabled;
+                self.persist_settings();
+            }
+            Message::DiagnosticsPathChanged(s) => self.diagnostics_path_input = s,
+            Message::SnapshotBeforeChanged(s) => self.snapshot_before_input = s,
+            Message::SnapshotAfterChanged(s) => self.snapshot_after_input = s,
+            Message::CompareSnapshots(before, after) => {
+                if before.as_os_str().is_empty() || after.as_os_str().is_empty() {
+                    self.show_toast("❌ Enter both CSV paths to compare".into());
+                    return Command::none();
+                }
+                return Command::perform(compare_snapshots(before, after), Message::SnapshotsCompared);
+            }
+            Message::SnapshotsCompared(result) => match result {
+                Ok(rows) => self.snapshot_diff = Some(rows),
+                Err(e) => self.show_toast(format!("❌ Compare failed: {}", e)),
+            },
+            Message::SettingsPathChanged(s) => self.settings_path_input = s,
+            Message::ExportSettings => {
+                let path = PathBuf::from(self.settings_path_input.trim());
+                if path.as_os_str().is_empty() {
+                    self.show_toast("❌ Enter a file path for the settings file".into());
+                    return Command::none();
+                }
+                return Command::perform(export_settings(path, self.current_config()), Message::SettingsExported);
+            }
+            Message::ImportSettings => {
+                let path = PathBuf::from(self.settings_path_input.trim());
+                if path.as_os_str().is_empty() {
+                    self.show_toast("❌ Enter a file path for the settings file".into());
+                    return Command::none();
+                }
+                return Command::perform(import_settings(path), Message::SettingsImported);
+            }
+            Message::SettingsExported(result) => {
+                self.show_toast(match result {
+                    Ok(path) => format!("✅ Settings exported to {}", path.display()),
+                    Err(e) => format!("❌ Couldn't export settings: {}", e),
+                });
+            }
+            Message::SettingsImported(result) => {
+                match result {
+                    // Only a fully validated config ever touches the live settings
+                    Ok(config) => {
+                        self.apply_config(config);
+                        self.show_toast(match self.save_settings() {
+                            Ok(()) => "✅ Settings imported".into(),
+                            Err(e) => format!("❌ Settings imported but couldn't be saved: {}", e),
+                        });
+                    }
+                    Err(e) => {
+                        self.show_toast(format!("❌ Couldn't import settings: {}", e));
+                    }
+                }
+            }
+            Message::SystemInformation(information) => {
+                info!(
+                    "Renderer: using {} on {}",
+                    information.graphics_backend, information.graphics_adapter
+                );
+            }
+            Message::DumpDiagnostics => {
+                let path = PathBuf::from(self.diagnostics_path_input.trim());
+                if path.as_os_str().is_empty() {
+                    self.show_toast("❌ Enter a file path for the diagnostics report".into());
+                    return Command::none();
+                }
+                // The list may be stale if the Processes tab wasn't open
+                self.after_process_refresh.push(Message::WriteDiagnostics(path));
+                return self.refresh_processes();
+            }
+            Message::WriteDiagnostics(path) => {
+                let report = self.build_diagnostics_report();
+                return Command::perform(write_diagnostics(path, report), Message::DiagnosticsComplete);
+            }
+            Message::DiagnosticsComplete(result) => {
+                match result {
+                    Ok(path) => {
+                        self.show_toast(format!("✅ Diagnostics written to {}", path.display()));
+                    }
+                    Err(e) => {
+                        self.show_toast(format!("❌ Couldn't write diagnostics: {}", e));
+                    }
+                }
+            }
+        }
+        Command::none()
+    }
+
+    // Build the UI based on current state
+    fn view(&self) -> Element<'_, Message> {
+        // Top navigation bar
+        let header = row![
+            text("System Monitor").size(20),
+            button("Overview").on_press(Message::GoToOverview).padding(5),
+            button("Processes").on_press(Message::GoToProcesses).padding(5),
+            button("Disks").on_press(Message::GoToDisks).padding(5),
+            horizontal_space(),
+            button("Settings").on_press(Message::GoToSettings).padding(8),
+        ]
+        .spacing(15)
+        .align_items(Alignment::Center)
+        .padding(12);
+
+        // Main content area - changes based on current tab
+        let content: Element<_> = match self.current_screen {
+            Screen::Overview => self.view_overview(),
+            Screen::Processes => self.view_processes(),
+            Screen::Disks => self.view_disks(),
+            Screen::Settings => self.view_settings(),
+        };
+
+        // Content fills the space between the header and the footer
+        // A maximized process list hides the nav to give the table the whole window
+        let mut main = if self.list_maximized_now() {
+            column![self.view_transition(content)]
+        } else {
+            let mut main = column![header];
+            let pending_restart = self.restart_required();
+            if !pending_restart.is_empty() {
+                main = main.push(self.view_restart_banner(&pending_restart));
+            }
+            if let Some(notes) = whats_new(self.last_seen_version.as_deref()) {
+                main = main.push(self.view_whats_new(notes));
+            }
+            main.push(self.view_transition(content))
+        };
 
         // Show toast messages if we have any (success/error popups)
         if let Some(toast_msg) = &self.toast_message {
             let is_error = toast_msg.contains('❌');
             let toast_color = if is_error { 
                 // Red for errors
-                if self.dark_mode { Color::from_rgb(1.0, 0.5, 0.5) } else { Color::from_rgb(0.8, 0.0, 0.0) }
+                error_color(self.dark_mode)
             } else { 
                 // Green for success
-                if self.dark_mode { Color::from_rgb(0.5, 1.0, 0.5) } else { Color::from_rgb(0.0, 0.6, 0.0) }
-            };
-            
-            let toast_bg = if self.dark_mode { 
-                Color::from_rgb(0.2, 0.2, 0.2)  // Dark gray background
-            } else { 
-                Color::from_rgb(0.98, 0.98, 0.98)  // Light gray background
-            };
-            let toast_border = if self.dark_mode { 
-                Color::from_rgb(0.4, 0.4, 0.4)  // Medium gray border
-            } else { 
-                Color::from_rgb(0.8, 0.8, 0.8)  // Light gray border
+                success_color(self.dark_mode)
             };
+            let toast_bg = toast_bg(self.dark_mode);
+            let toast_border = toast_border(self.dark_mode);
 
             let toast = container(text(toast_msg).size(14).style(toast_color))
                 .padding(10)
@@ -316,6 +1643,11 @@ impl Application for LightMon {
             main = main.push(toast);
         }
 
+        // Compact stats footer, visible on every tab
+        if self.show_status_bar {
+            main = main.push(self.view_status_bar());
+        }
+
         container(main)
             .width(Length::Fill)
             .height(Length::Fill)
@@ -323,10 +1655,46 @@ impl Application for LightMon {
             .into()
     }
 
-    // Set up periodic updates
+    // Set up periodic updates and keyboard shortcuts
     fn subscription(&self) -> Subscription<Message> {
-        time::every(std::time::Duration::from_secs(self.refresh_interval))
-            .map(|_| Message::Tick)
+        Subscription::batch(vec![
+            time::every(Duration::from_secs(self.effective_refresh_interval()))
+                .map(|_| Message::Tick),
+            // Nobody sees the process list on the other tabs, so don't pay for it there
+            if matches!(self.current_screen, Screen::Processes) {
+                time::every(Duration::from_secs(self.effective_process_refresh_interval()))
+                    .map(|_| Message::ProcessTick)
+            } else {
+                Subscription::none()
+            },
+            keyboard::on_key_press(keyboard_shortcut),
+            event::listen_with(window_event),
+            // Redraw every frame, but only while a tab transition is running
+            if self.transition_started.is_some() {
+                window::frames().map(Message::AnimationFrame)
+            } else {
+                Subscription::none()
+            },
+            // Keep the "next refresh in Xs" countdown moving between ticks
+            if matches!(self.current_screen, Screen::Overview) && self.effective_refresh_interval() > 1 {
+                time::every(Duration::from_secs(1)).map(|_| Message::CountdownTick)
+            } else {
+                Subscription::none()
+            },
+            // Only ticks while a toast is up
+            if self.toast_message.is_some() {
+                time::every(TOAST_CHECK_INTERVAL).map(Message::ToastTick)
+            } else {
+                Subscription::none()
+            },
+            // Independent of the display refresh; Subscription::none() when off
+            if self.auto_export_minutes > 0 {
+                time::every(std::time::Duration::from_secs(self.auto_export_minutes.saturating_mul(60)))
+                    .map(|_| Message::AutoExportTick)
+            } else {
+                Subscription::none()
+            },
+        ])
     }
 
     // Return current theme
@@ -339,505 +1707,3759 @@ impl Application for LightMon {
     }
 }
 
-async fn fetch_system_data() -> (f32, u64, u64, u64, u64) {
-    let mut sys = System::new_all();
-    sys.refresh_all();
+// Theme-aware colors, shared by every screen so light/dark stay consistent
+// Muted text for labels and hints - needs to stay readable on both panel backgrounds
+fn label_color(dark_mode: bool) -> Color {
+    if dark_mode { Color::from_rgb(0.7, 0.7, 0.7) } else { Color::from_rgb(0.4, 0.4, 0.4) }
+}
+
+// Background and border of the bordered panels (process list, details, settings)
+fn panel_bg(dark_mode: bool) -> Color {
+    if dark_mode { Color::from_rgb(0.15, 0.15, 0.15) } else { Color::from_rgb(0.95, 0.95, 0.95) }
+}
 
-    // CPU
-    let cpu = sys.cpus().first().map(|c| c.cpu_usage()).unwrap_or(0.0);
+fn panel_border(dark_mode: bool) -> Color {
+    if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.2, 0.2, 0.2) }
+}
 
-    // Memory (KB)
-    let used_mem_kb = sys.used_memory();
-    let total_mem_kb = sys.total_memory();
+// Overview stat boxes and the status bar
+fn card_bg(dark_mode: bool) -> Color {
+    if dark_mode { Color::from_rgb(0.12, 0.12, 0.12) } else { Color::from_rgb(0.95, 0.95, 0.95) }
+}
 
-    // Disks (new API: separate Disks struct)
-    let mut disks = Disks::new_with_refreshed_list();
-    disks.refresh();
+fn card_text(dark_mode: bool) -> Color {
+    if dark_mode { Color::from_rgb(0.94, 0.94, 0.94) } else { Color::from_rgb(0.06, 0.06, 0.06) }
+}
+
+fn card_border(dark_mode: bool) -> Color {
+    if dark_mode { Color::from_rgb(0.25, 0.25, 0.25) } else { Color::from_rgb(0.2, 0.2, 0.2) }
+}
+
+// Lighter outline for things that shouldn't draw the eye (status bar)
+fn subtle_border(dark_mode: bool) -> Color {
+    if dark_mode { Color::from_rgb(0.25, 0.25, 0.25) } else { Color::from_rgb(0.8, 0.8, 0.8) }
+}
+
+// Toast box behind the success/error text
+fn toast_bg(dark_mode: bool) -> Color {
+    if dark_mode { Color::from_rgb(0.2, 0.2, 0.2) } else { Color::from_rgb(0.98, 0.98, 0.98) }
+}
+
+fn toast_border(dark_mode: bool) -> Color {
+    if dark_mode { Color::from_rgb(0.4, 0.4, 0.4) } else { Color::from_rgb(0.8, 0.8, 0.8) }
+}
+
+// Toast text; error_color also marks invalid setting values
+fn error_color(dark_mode: bool) -> Color {
+    if dark_mode { Color::from_rgb(1.0, 0.5, 0.5) } else { Color::from_rgb(0.8, 0.0, 0.0) }
+}
+
+fn success_color(dark_mode: bool) -> Color {
+    if dark_mode { Color::from_rgb(0.5, 1.0, 0.5) } else { Color::from_rgb(0.0, 0.6, 0.0) }
+}
+
+// Allowed ranges for numeric settings (inclusive)
+const REFRESH_INTERVAL_RANGE: (u64, u64) = (1, 3600);  // seconds
+const PROCESS_REFRESH_RANGE: (u64, u64) = (0, 3600);   // seconds, 0 = same as the update frequency
+const AUTO_EXPORT_RANGE: (u64, u64) = (0, 1440);       // minutes, 0 = off
+const CPU_ALPHA_RANGE: (f32, f32) = (0.01, 1.0);
+const NEW_PROCESS_HIGHLIGHT_RANGE: (u64, u64) = (0, 300);  // seconds, 0 = off
+const IDLE_THRESHOLD_RANGE: (f32, f32) = (0.0, 100.0);     // CPU %, 0 = off
+const HISTORY_LEN_RANGE: (usize, usize) = (2, 3600);       // samples
+const NAME_MAX_CHARS_RANGE: (usize, usize) = (0, 500);     // characters, 0 = off
+const ALERT_THRESHOLD_RANGE: (f32, f32) = (0.0, 100.0);    // percent, 0 = off
 
-    let mut total_disk_bytes: u64 = 0;
-    let mut used_disk_bytes: u64 = 0;
+// Parse a numeric setting, explaining what's wrong if it's not usable
+fn parse_in_range<T>(input: &str, range: (T, T)) -> Result<T, String>
+where
+    T: std::str::FromStr + PartialOrd + std::fmt::Display + Copy,
+{
+    let value: T = input.trim().parse().map_err(|_| out_of_range_hint(range))?;
+    check_in_range(value, range)
+}
 
-    for disk in disks.list() {
-        let t = disk.total_space();
-        let a = disk.available_space();
-        total_disk_bytes = total_disk_bytes.saturating_add(t);
-        used_disk_bytes = used_disk_bytes.saturating_add(t.saturating_sub(a));
+// Range check shared by typed input and loaded configs. NaN compares false with
+// everything, so it fails `contains`; infinities fail it because every range is finite
+fn check_in_range<T>(value: T, range: (T, T)) -> Result<T, String>
+where
+    T: PartialOrd + std::fmt::Display + Copy,
+{
+    if !(range.0..=range.1).contains(&value) {
+        return Err(out_of_range_hint(range));
     }
+    Ok(value)
+}
 
-    (cpu, used_mem_kb, total_mem_kb, used_disk_bytes, total_disk_bytes)
+fn out_of_range_hint<T: std::fmt::Display>((min, max): (T, T)) -> String {
+    format!("must be {}–{}", min, max)
 }
 
+// Numeric text input shared by all numeric settings
+// Invalid values get a red border and a hint instead of being silently ignored
+fn validated_number_input<'a, T>(
+    placeholder: &str,
+    value: &str,
+    range: (T, T),
+    on_input: fn(String) -> Message,
+    width: f32,
+    dark_mode: bool,
+) -> Element<'a, Message>
+where
+    T: std::str::FromStr + PartialOrd + std::fmt::Display + Copy,
+{
+    let error = parse_in_range(value, range).err();
 
+    let mut input = text_input(placeholder, value)
+        .on_input(on_input)
+        .padding(10)
+        .size(14)
+        .width(Length::Fixed(width));
+    if error.is_some() {
+        input = input.style(iced::theme::TextInput::Custom(Box::new(InvalidInput)));
+    }
 
-impl LightMon {
-    // Kill a process using Windows taskkill command
-    fn kill_process(&self, pid: Pid) -> Result<(), String> {
-        let output = ProcessCommand::new("taskkill")
-            .args(&["/PID", &pid.to_string(), "/F"])  // /F = force kill
-            .output()
-            .map_err(|e| format!("Failed to run taskkill: {}", e))?;
-        
-        if output.status.success() {
-            info!("Successfully killed process {}", pid);
-            Ok(())
-        } else {
-            let error_msg = String::from_utf8_lossy(&output.stderr);
-            Err(format!("Windows taskkill failed: {}", error_msg))
-        }
+    match error {
+        Some(hint) => column![
+            input,
+            text(hint).size(12).style(error_color(dark_mode)),
+        ].spacing(4).into(),
+        None => input.into(),
     }
+}
 
-    // Get process data for CSV export
-    fn get_processes_data(&self) -> Vec<(Pid, String, f32, u64, String)> {
-        self.sys.processes()
-            .iter()
-            .map(|(pid, process)| {
-                (
-                    *pid,
-                    process.name().to_string(),
-                    process.cpu_usage(),
-                    process.memory(),
-                    format!("{:?}", process.status())
-                )
-            })
-            .collect()
+// Whether a Settings section should show for the search text
+// Every word typed has to appear in the title or keywords; empty shows everything
+fn settings_section_matches(title: &str, keywords: &str, query: &str) -> bool {
+    let haystack = format!("{} {}", title, keywords).to_lowercase();
+    query.to_lowercase().split_whitespace().all(|word| haystack.contains(word))
+}
+
+// Case-insensitive match on name, or substring match on the PID
+fn matches_filter(name: &str, pid: Pid, filter: &str) -> bool {
+    name.to_lowercase().contains(&filter.to_lowercase()) || pid.to_string().contains(filter)
+}
+
+// Highlight for the selected process row - lighter fill with a strong outline
+struct SelectedRow;
+
+impl button_style::StyleSheet for SelectedRow {
+    type Style = Theme;
+
+    fn active(&self, theme: &Theme) -> button_style::Appearance {
+        let palette = theme.extended_palette();
+        button_style::Appearance {
+            background: Some(Background::Color(palette.primary.weak.color)),
+            text_color: palette.primary.weak.text,
+            border: Border {
+                color: palette.primary.strong.color,
+                width: 2.0,
+                radius: 2.0.into(),
+            },
+            ..Default::default()
+        }
     }
+}
 
-    // Overview tab - shows system resource usage
-    fn view_overview(&self) -> Element<Message> {
-        // Calculate percentages and convert units
-        // convert memory KB -> GB
-        let mem_total_gb = (self.memory_total as f64) / 1024.0 / 1024.0;
-        let mem_used_gb  = (self.memory_used as f64)  / 1024.0 / 1024.0;
-        let mem_percent = if mem_total_gb > 0.0 {
-            (mem_used_gb / mem_total_gb * 100.0).min(100.0)
-        } else { 0.0 };
+// Ctrl-click multi-selection - secondary color, so it reads differently from
+// the single selection that drives the details panel
+struct MultiSelectedRow;
 
-        // convert disk bytes -> GB
-        let disk_total_gb = (self.disk_total as f64) / 1024.0 / 1024.0 / 1024.0;
-        let disk_used_gb  = (self.disk_used as f64)  / 1024.0 / 1024.0 / 1024.0;
-        let disk_percent = if disk_total_gb > 0.0 {
-            (disk_used_gb / disk_total_gb * 100.0).min(100.0)
-        } else { 0.0 };
+impl button_style::StyleSheet for MultiSelectedRow {
+    type Style = Theme;
 
+    fn active(&self, theme: &Theme) -> button_style::Appearance {
+        let palette = theme.extended_palette();
+        button_style::Appearance {
+            background: Some(Background::Color(palette.secondary.weak.color)),
+            text_color: palette.secondary.weak.text,
+            border: Border {
+                color: palette.secondary.strong.color,
+                width: 1.0,
+                radius: 2.0.into(),
+            },
+            ..Default::default()
+        }
+    }
+}
 
-        // Create visual progress bars
-        let cpu_filled = (self.cpu_usage as usize / 5).min(20);
-        let mem_filled = (mem_percent as usize / 5).min(20);
-        let disk_filled = (disk_percent as usize / 5).min(20);
+// Subtle tint for processes that started in the last few seconds
+struct NewProcessRow;
 
-        let cpu_bar = format!("[{}{}]", "█".repeat(cpu_filled), "░".repeat(20 - cpu_filled));
-        let mem_bar = format!("[{}{}]", "█".repeat(mem_filled), "░".repeat(20 - mem_filled));
-        let disk_bar = format!("[{}{}]", "█".repeat(disk_filled), "░".repeat(20 - disk_filled));
+impl button_style::StyleSheet for NewProcessRow {
+    type Style = Theme;
 
-        // Helper to create consistent stat boxes
-        let stat_box = |label: &str, bar: String, percent: f32| {
-            let bg = if self.dark_mode { 
-                Color::from_rgb(0.12, 0.12, 0.12)  // Very dark gray
-            } else { 
-                Color::from_rgb(0.95, 0.95, 0.95)  // Very light gray
-            };
-            let text_color = if self.dark_mode { 
-                Color::from_rgb(0.94, 0.94, 0.94)  // Almost white
-            } else { 
-                Color::from_rgb(0.06, 0.06, 0.06)  // Almost black
-            };
-            let border_color = if self.dark_mode { 
-                Color::from_rgb(0.25, 0.25, 0.25)  // Medium dark gray
-            } else { 
-                Color::from_rgb(0.2, 0.2, 0.2)     // Medium light gray
-            };
+    fn active(&self, theme: &Theme) -> button_style::Appearance {
+        let palette = theme.extended_palette();
+        let mut tint = palette.success.weak.color;
+        tint.a = 0.35;  // Keep it faint so the selection highlight still stands out
+        button_style::Appearance {
+            background: Some(Background::Color(tint)),
+            text_color: palette.background.base.text,
+            border: Border {
+                radius: 2.0.into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
 
-            container(
-                column![
-                    text(label).size(16),
-                    row![
-                        text(bar).size(16),  // The progress bar
-                        text(format!("{:.1}%", percent)).width(Length::Fixed(70.0)).size(16),  // Percentage
-                    ].spacing(12).align_items(Alignment::Center),
-                ].spacing(6)
-            )
-            .padding(14)
-            .style(move |_theme: &Theme| Appearance {
-                text_color: Some(text_color),
-                background: Some(Background::Color(bg)),
-                border: Border { 
-                    color: border_color, 
-                    width: 1.0, 
-                    radius: 4.0.into() 
-                },
-                shadow: Default::default(),
-            })
-        };
+// Normal row look with faded text, for processes below the idle threshold
+// Fading the theme's own text color keeps the contrast right in light and dark mode
+struct IdleRow;
 
-        column![
-            text("System Overview").size(28),
-            vertical_space().height(Length::Fixed(10.0)),
-            stat_box("CPU", cpu_bar, self.cpu_usage),
-            stat_box("Memory", mem_bar, mem_percent as f32),
-            stat_box("Disk", disk_bar, disk_percent as f32),
-            vertical_space().height(Length::Fixed(15.0)),
-            text(format!("Memory: {:.1} / {:.1} GB", mem_used_gb, mem_total_gb))
-                .size(14),
-        ]
-        .spacing(8)
-        .padding(25)
-        .align_items(Alignment::Start)
-        .into()
+impl IdleRow {
+    fn dimmed(mut appearance: button_style::Appearance) -> button_style::Appearance {
+        appearance.text_color.a = 0.55;
+        appearance
     }
+}
 
-    // Processes tab - list and manage running processes
-    fn view_processes(&self) -> Element<Message> {
-        let mut content_column = column![
-            text("Running Processes").size(28),
-            vertical_space().height(Length::Fixed(10.0)),
-            // Action buttons
-            row![
-                button("Sort by CPU").on_press(Message::SortByCpu).padding(6),
-                button("Sort by Memory").on_press(Message::SortByMemory).padding(6),
-                if self.is_exporting {
-                    button("Exporting...").padding(6)  // Show loading state
-                } else {
-                    button("Export to CSV").on_press(Message::ExportProcesses).padding(6)
-                },
-            ].spacing(10),
-            vertical_space().height(Length::Fixed(10.0)),
-            // Search box
-            text_input("Search processes by name or PID", &self.filter_text)
-                .on_input(Message::FilterChanged)
-                .padding(10)
-                .size(15),
-            vertical_space().height(Length::Fixed(10.0)),
-        ].spacing(6).padding(25);
+impl button_style::StyleSheet for IdleRow {
+    type Style = Theme;
 
-        // Process list header
-        let mut process_list = column![
-            row![
-                text("PID").width(Length::Fixed(80.0)).size(15),
-                text("Name").width(Length::Fill).size(15),
-                text("CPU%").width(Length::Fixed(80.0)).size(15),
-                text("Memory").width(Length::Fixed(100.0)).size(15),
-            ].spacing(12).align_items(Alignment::Center),
-        ].spacing(8);
+    fn active(&self, theme: &Theme) -> button_style::Appearance {
+        Self::dimmed(button_style::StyleSheet::active(theme, &Default::default()))
+    }
 
-        // Sort processes based on current setting
-        let mut processes: Vec<_> = self.sys.processes().iter().collect();
-        match self.sort_by {
-            SortBy::Cpu => processes.sort_by(|a, b| {
-                b.1.cpu_usage().partial_cmp(&a.1.cpu_usage()).unwrap_or(std::cmp::Ordering::Equal)
-            }),
-            SortBy::Memory => processes.sort_by(|a, b| b.1.memory().cmp(&a.1.memory())),
+    fn hovered(&self, theme: &Theme) -> button_style::Appearance {
+        Self::dimmed(button_style::StyleSheet::hovered(theme, &Default::default()))
+    }
+}
+
+// Whether a process is quiet enough to be dimmed; a threshold of 0 never dims
+fn is_idle(cpu_usage: f32, threshold: f32) -> bool {
+    cpu_usage < threshold
+}
+
+// Default text input look, but with a red (theme "danger") border
+struct InvalidInput;
+
+impl InvalidInput {
+    fn with_danger_border(theme: &Theme, mut appearance: text_input_style::Appearance) -> text_input_style::Appearance {
+        appearance.border.color = theme.extended_palette().danger.base.color;
+        appearance.border.width = 2.0;
+        appearance
+    }
+}
+
+impl text_input_style::StyleSheet for InvalidInput {
+    type Style = Theme;
+
+    fn active(&self, theme: &Theme) -> text_input_style::Appearance {
+        Self::with_danger_border(theme, theme.active(&Default::default()))
+    }
+
+    fn focused(&self, theme: &Theme) -> text_input_style::Appearance {
+        Self::with_danger_border(theme, theme.focused(&Default::default()))
+    }
+
+    fn hovered(&self, theme: &Theme) -> text_input_style::Appearance {
+        Self::with_danger_border(theme, theme.hovered(&Default::default()))
+    }
+
+    fn disabled(&self, theme: &Theme) -> text_input_style::Appearance {
+        theme.disabled(&Default::default())
+    }
+
+    fn placeholder_color(&self, theme: &Theme) -> Color {
+        theme.placeholder_color(&Default::default())
+    }
+
+    fn value_color(&self, theme: &Theme) -> Color {
+        theme.value_color(&Default::default())
+    }
+
+    fn disabled_color(&self, theme: &Theme) -> Color {
+        theme.disabled_color(&Default::default())
+    }
+
+    fn selection_color(&self, theme: &Theme) -> Color {
+        theme.selection_color(&Default::default())
+    }
+}
+
+// Bytes per second over the actual elapsed time; 0 if no time has passed
+fn rate(delta_bytes: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs > 0.0 { delta_bytes as f64 / secs } else { 0.0 }
+}
+
+// Exponential moving average step: alpha weights the new sample.
+// clamp() passes NaN through, and a NaN average would stick for the rest of the
+// run, so a non-finite alpha (e.g. hand-edited config) or average restarts from the sample
+fn ema(prev: f32, sample: f32, alpha: f32) -> f32 {
+    if !alpha.is_finite() || !prev.is_finite() {
+        return sample;
+    }
+    let alpha = alpha.clamp(0.0, 1.0);
+    alpha * sample + (1.0 - alpha) * prev
+}
+
+// 20-cell text bar like "[████░░░...]", one cell per 5%
+fn text_bar(percent: f64, filled: &str, empty: &str) -> String {
+    const CELLS: usize = 20;
+    let percent = if percent.is_finite() { percent.clamp(0.0, 100.0) } else { 0.0 };
+    let filled_cells = ((percent as usize) / 5).min(CELLS);
+    format!("[{}{}]", filled.repeat(filled_cells), empty.repeat(CELLS - filled_cells))
+}
+
+// Whole seconds until the next tick is due, rounded up so it never shows 0
+// while still waiting. None before the first snapshot
+fn seconds_until_refresh(last_snapshot: Option<Instant>, interval_secs: u64, now: Instant) -> Option<u64> {
+    let due = last_snapshot? + Duration::from_secs(interval_secs);
+    let remaining = due.saturating_duration_since(now);
+    Some(remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0))
+}
+
+// Append a sample, dropping the oldest so the deque never exceeds `cap`.
+// This is what keeps the app's own memory use flat on long runs
+fn push_bounded<T>(history: &mut VecDeque<T>, value: T, cap: usize) {
+    history.push_back(value);
+    while history.len() > cap {
+        history.pop_front();
+    }
+}
+
+// Characters for an 8-level sparkline, lowest to highest
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const SPARKLINE_WIDTH: usize = 60;
+
+// Text sparkline of 0-100 values, at most `width` characters wide.
+// Longer histories are averaged into buckets so the whole range still fits
+fn sparkline<'a>(values: impl Iterator<Item = &'a f32>, width: usize) -> String {
+    let values: Vec<f32> = values.copied().collect();
+    if values.is_empty() || width == 0 {
+        return String::new();
+    }
+    let bucket = values.len().div_ceil(width);
+    values
+        .chunks(bucket)
+        .map(|chunk| {
+            let avg = chunk.iter().sum::<f32>() / chunk.len() as f32;
+            let level = (avg.clamp(0.0, 100.0) / 100.0 * (SPARK_LEVELS.len() - 1) as f32).round() as usize;
+            SPARK_LEVELS[level]
+        })
+        .collect()
+}
+
+// Per-process CPU as shown in the table. sysinfo reports 100% per fully busy
+// core, so 130% is 1.3 cores - often clearer on many-core machines
+fn format_process_cpu(cpu_usage: f32, as_cores: bool) -> String {
+    if as_cores {
+        format!("{:.2}", cpu_usage / 100.0)
+    } else {
+        format!("{:.1}", cpu_usage)
+    }
+}
+
+// Column header matching format_process_cpu
+fn process_cpu_label(as_cores: bool) -> &'static str {
+    if as_cores { "Cores" } else { "CPU%" }
+}
+
+// Shorten to at most max characters (counting the "…") by cutting the end,
+// e.g. ("chrome_crashpad_handler", 10) -> "chrome_cr…"
+fn truncate_end(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+    let mut out: String = s.chars().take(max - 1).collect();
+    out.push('…');
+    out
+}
+
+// Same, but cut from the middle so both ends stay readable - better for paths,
+// e.g. ("/home/me/exports/processes.csv", 16) -> "/home/me…ses.csv"
+fn truncate_middle(s: &str, max: usize) -> String {
+    let len = s.chars().count();
+    if len <= max {
+        return s.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+    let keep = max - 1;
+    let head = keep.div_ceil(2);
+    let tail = keep / 2;
+    let mut out: String = s.chars().take(head).collect();
+    out.push('…');
+    out.extend(s.chars().skip(len - tail));
+    out
+}
+
+// Human-readable size, e.g. 1536 -> "1.5 KB"
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+// Compact duration, e.g. 3725 -> "1h 2m 5s"
+fn format_duration(total_secs: u64) -> String {
+    let days = total_secs / 86_400;
+    let hours = (total_secs % 86_400) / 3_600;
+    let minutes = (total_secs % 3_600) / 60;
+    let secs = total_secs % 60;
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, secs)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, secs)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+// Shared memory of a process in bytes, where the OS reports it
+// Linux: third field of /proc/<pid>/statm (in pages). sysinfo doesn't expose this.
+#[cfg(target_os = "linux")]
+fn process_shared_memory(pid: Pid) -> Option<u64> {
+    let statm = fs::read_to_string(format!("/proc/{}/statm", pid)).ok()?;
+    let shared_pages: u64 = statm.split_whitespace().nth(2)?.parse().ok()?;
+    // SAFETY: sysconf has no preconditions
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if page_size <= 0 {
+        return None;
+    }
+    Some(shared_pages.saturating_mul(page_size as u64))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_shared_memory(_pid: Pid) -> Option<u64> {
+    None  // Not distinguished on this platform
+}
+
+// Nice value (-20 highest priority .. 19 lowest) of a process, where the OS reports it
+// Linux: field 19 of /proc/<pid>/stat. The name (field 2) may contain spaces
+// and parens, so count fields from the last ')'
+#[cfg(target_os = "linux")]
+fn process_nice(pid: Pid) -> Option<i32> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let after_name = &stat[stat.rfind(')')? + 1..];
+    after_name.split_whitespace().nth(16)?.parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_nice(_pid: Pid) -> Option<i32> {
+    None  // Not read on this platform yet
+}
+
+// Open file descriptors (Linux) or handles (Windows) of a process - a number that
+// keeps climbing points at a leak. None where it can't be read (permissions, other OSes)
+#[cfg(target_os = "linux")]
+fn open_handle_count(pid: Pid) -> Option<usize> {
+    fs::read_dir(format!("/proc/{}/fd", pid)).ok().map(|entries| entries.count())
+}
+
+#[cfg(windows)]
+fn open_handle_count(pid: Pid) -> Option<usize> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{GetProcessHandleCount, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+    // SAFETY: the handle is checked before use and closed on every path
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid.as_u32());
+        if handle.is_null() {
+            return None;
         }
+        let mut count = 0u32;
+        let ok = GetProcessHandleCount(handle, &mut count);
+        CloseHandle(handle);
+        (ok != 0).then_some(count as usize)
+    }
+}
 
-        // Filter processes based on search text
-        let filter = self.filter_text.to_lowercase();
-        let filtered = processes.into_iter().filter(|(_, p)| {
-            let pid_str = format!("{}", p.pid());
-            p.name().to_lowercase().contains(&filter) || pid_str.contains(&filter)
-        });
+#[cfg(not(any(target_os = "linux", windows)))]
+fn open_handle_count(_pid: Pid) -> Option<usize> {
+    None  // Not read on this platform yet
+}
 
-        // Display processes (limit to 12 for performance)
-        for (pid, process) in filtered.take(12) {
-            let row_content = row![
-                text(format!("{}", pid)).width(Length::Fixed(80.0)).size(14),
-                text(process.name()).width(Length::Fill).size(14),
-                text(format!("{:.1}", process.cpu_usage())).width(Length::Fixed(80.0)).size(14),
-                text(format!("{} MB", process.memory() / 1024)).width(Length::Fixed(100.0)).size(14),
-            ].spacing(12).align_items(Alignment::Center);
-
-            // Make each row clickable
-            let row_button = button(row_content)
-                .on_press(Message::SelectProcess(*pid))
-                .padding(4);
+// Details-panel values that come from the OS rather than sysinfo.
+// Re-read after every process refresh so they track the process over time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct SelectedProcessInfo {
+    nice: Option<i32>,
+    open_handles: Option<usize>,
+    shared_memory: Option<u64>,
+}
 
-            process_list = process_list.push(row_button);
+// These are file reads (and listing a busy process's fd directory can take a
+// while), so they run as a task and view() only shows the cached result
+async fn fetch_selected_info(pid: Pid) -> SelectedProcessInfo {
+    SelectedProcessInfo {
+        nice: process_nice(pid),
+        open_handles: open_handle_count(pid),
+        shared_memory: process_shared_memory(pid),
+    }
+}
+
+// Processes whose death takes the session (or the whole OS) down with them.
+// Killing one asks for confirmation first; users can add more in Settings
+const BUILTIN_CRITICAL_PROCESSES: &[&str] = &[
+    // Linux / Unix
+    "systemd", "init", "kthreadd", "Xorg", "Xwayland", "gnome-shell", "kwin_x11", "kwin_wayland", "sshd",
+    // macOS
+    "launchd", "kernel_task", "WindowServer", "loginwindow",
+    // Windows
+    "System", "smss.exe", "csrss.exe", "wininit.exe", "winlogon.exe", "services.exe", "lsass.exe",
+    "svchost.exe", "explorer.exe", "dwm.exe",
+];
+
+// Renicing goes through setpriority, which only exists on Unix
+const RENICE_SUPPORTED: bool = cfg!(unix);
+const NICE_RANGE: (i32, i32) = (-20, 19);
+
+#[cfg(unix)]
+fn set_process_nice(pid: Pid, nice: i32) -> Result<(), String> {
+    // SAFETY: setpriority only reads its arguments; a bad PID is reported via errno
+    let result = unsafe { libc::setpriority(libc::PRIO_PROCESS, pid.as_u32() as libc::id_t, nice) };
+    if result == 0 {
+        info!("Set nice of process {} to {}", pid, nice);
+        return Ok(());
+    }
+    let err = std::io::Error::last_os_error();
+    match err.kind() {
+        std::io::ErrorKind::PermissionDenied => {
+            Err("permission denied - raising priority (negative nice) or changing other users' processes needs root".into())
         }
+        _ => Err(err.to_string()),
+    }
+}
 
-        // Style the process list container
-        let list_bg = if self.dark_mode { 
-            Color::from_rgb(0.15, 0.15, 0.15) 
-        } else { 
-            Color::from_rgb(0.95, 0.95, 0.95) 
+#[cfg(not(unix))]
+fn set_process_nice(_pid: Pid, _nice: i32) -> Result<(), String> {
+    Err("changing priority isn't supported on this platform".into())
+}
+
+// Tab switches slide the new screen in from the right over this long
+const TRANSITION_DURATION: Duration = Duration::from_millis(180);
+const TRANSITION_DISTANCE: f32 = 24.0;  // pixels
+
+// Left offset of the content during a transition: starts at TRANSITION_DISTANCE
+// and eases out to 0. Zero when nothing is animating
+fn transition_offset(started: Option<Instant>, now: Instant) -> f32 {
+    let Some(started) = started else {
+        return 0.0;
+    };
+    let progress = (now.saturating_duration_since(started).as_secs_f32() / TRANSITION_DURATION.as_secs_f32()).min(1.0);
+    let remaining = 1.0 - progress;
+    TRANSITION_DISTANCE * remaining * remaining  // Ease-out: fast start, gentle stop
+}
+
+// Whether the OS asks apps to cut down on animation, where we know how to ask.
+// Anything unreadable counts as "no preference"
+#[cfg(target_os = "linux")]
+fn os_prefers_reduced_motion() -> bool {
+    // GNOME (and most GTK desktops): enable-animations false
+    ProcessCommand::new("gsettings")
+        .args(["get", "org.gnome.desktop.interface", "enable-animations"])
+        .output()
+        .is_ok_and(|out| String::from_utf8_lossy(&out.stdout).trim() == "false")
+}
+
+#[cfg(target_os = "macos")]
+fn os_prefers_reduced_motion() -> bool {
+    // Accessibility > Display > Reduce motion
+    ProcessCommand::new("defaults")
+        .args(["read", "com.apple.universalaccess", "reduceMotion"])
+        .output()
+        .is_ok_and(|out| String::from_utf8_lossy(&out.stdout).trim() == "1")
+}
+
+#[cfg(target_os = "windows")]
+fn os_prefers_reduced_motion() -> bool {
+    // "Animate controls and elements inside windows" off sets MinAnimate to 0
+    ProcessCommand::new("reg")
+        .args(["query", r"HKCU\Control Panel\Desktop\WindowMetrics", "/v", "MinAnimate"])
+        .output()
+        .is_ok_and(|out| {
+            String::from_utf8_lossy(&out.stdout)
+                .split_whitespace()
+                .last()
+                .is_some_and(|value| value == "0")
+        })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn os_prefers_reduced_motion() -> bool {
+    false
+}
+
+// Whether a new alert should fire for this sample. Fires once when the value
+// reaches the threshold and re-arms only after it drops back below, so a
+// sustained breach doesn't alert on every tick. A threshold of 0 is off
+fn alert_crossed(active: &mut bool, value: f32, threshold: f32) -> bool {
+    if threshold <= 0.0 {
+        *active = false;
+        return false;
+    }
+    let over = value >= threshold;
+    let fire = over && !*active;
+    *active = over;
+    fire
+}
+
+// Pop up a desktop notification using whatever the OS ships with. We don't use the
+// notify-rust crate: it brings a D-Bus client stack (zbus) on Linux and WinRT bindings
+// on Windows into an otherwise small dependency tree, while every OS we target already
+// has a notifier we can call. Each of them hands the notification to the OS and exits
+// right away. This runs as a task that waits for that, so nothing is left behind as a
+// zombie. An Err (e.g. no notification daemon) only gets logged
+async fn send_desktop_notification(title: String, body: String) -> Result<(), String> {
+    let status = tokio::task::spawn_blocking(move || notification_command(&title, &body).status())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("notifier exited with {}", status))
+    }
+}
+
+// The notifier invocation. Title and body are handed over as separate arguments
+// (or environment variables) and never spliced into a script, so quotes or
+// script syntax in a value are shown as-is
+#[cfg(target_os = "macos")]
+fn notification_command(title: &str, body: &str) -> ProcessCommand {
+    let mut command = ProcessCommand::new("osascript");
+    command.args([
+        "-e", "on run argv",
+        "-e", "display notification (item 2 of argv) with title (item 1 of argv)",
+        "-e", "end run",
+        title, body,
+    ]);
+    command
+}
+
+// Windows: a WinRT toast, which the OS keeps showing (and files in the notification
+// center) after PowerShell exits. Posted under PowerShell's app ID, since toasts need
+// a registered one and LightMon doesn't install a shortcut that would register its own
+#[cfg(target_os = "windows")]
+fn notification_command(title: &str, body: &str) -> ProcessCommand {
+    let mut command = ProcessCommand::new("powershell");
+    command
+        .args([
+            "-NoProfile",
+            "-WindowStyle",
+            "Hidden",
+            "-Command",
+            "$ErrorActionPreference = 'Stop'; \
+             $m = [Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime]; \
+             $xml = $m::GetTemplateContent([Windows.UI.Notifications.ToastTemplateType]::ToastText02); \
+             $text = $xml.GetElementsByTagName('text'); \
+             [void]$text.Item(0).AppendChild($xml.CreateTextNode($env:LIGHTMON_NOTIFY_TITLE)); \
+             [void]$text.Item(1).AppendChild($xml.CreateTextNode($env:LIGHTMON_NOTIFY_BODY)); \
+             $app = '{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\\WindowsPowerShell\\v1.0\\powershell.exe'; \
+             $m::CreateToastNotifier($app).Show([Windows.UI.Notifications.ToastNotification]::new($xml))",
+        ])
+        .env("LIGHTMON_NOTIFY_TITLE", title)
+        .env("LIGHTMON_NOTIFY_BODY", body);
+    command
+}
+
+// Linux and the BSDs: libnotify's CLI. `--` keeps a value starting with '-' from being read as a flag
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn notification_command(title: &str, body: &str) -> ProcessCommand {
+    let mut command = ProcessCommand::new("notify-send");
+    command.args(["--app-name=LightMon", "--", title, body]);
+    command
+}
+
+// How much slower we refresh in the background when throttling is on
+const UNFOCUSED_REFRESH_FACTOR: u64 = 5;
+
+// How long a toast stays up, and how often we check
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+const TOAST_CHECK_INTERVAL: Duration = Duration::from_millis(250);
+
+// Below this width the process table drops the Memory column and stacks PID under the name
+const NARROW_BREAKPOINT: f32 = 600.0;
+
+fn is_narrow(width: f32) -> bool {
+    width < NARROW_BREAKPOINT
+}
+
+// Process rows shown in the normal layout
+const DEFAULT_VISIBLE_ROWS: usize = 12;
+
+// Rough height of one process row and of everything above the rows when the
+// list is maximized (title, buttons, search box, column headers, padding)
+const PROCESS_ROW_HEIGHT: f32 = 34.0;
+const MAXIMIZED_CHROME_HEIGHT: f32 = 260.0;
+
+// How many process rows to render; a maximized list fills the window height
+fn visible_row_count(window_height: f32, maximized: bool) -> usize {
+    if !maximized {
+        return DEFAULT_VISIBLE_ROWS;
+    }
+    let fit = ((window_height - MAXIMIZED_CHROME_HEIGHT) / PROCESS_ROW_HEIGHT).floor().max(0.0) as usize;
+    fit.max(DEFAULT_VISIBLE_ROWS)
+}
+
+// Map window events we care about to messages
+fn window_event(event: iced::Event, _status: event::Status) -> Option<Message> {
+    match event {
+        iced::Event::Window(_, window::Event::Resized { width, height }) => {
+            Some(Message::WindowResized(width as f32, height as f32))
+        }
+        iced::Event::Window(_, window::Event::Focused) => Some(Message::WindowFocusChanged(true)),
+        iced::Event::Window(_, window::Event::Unfocused) => Some(Message::WindowFocusChanged(false)),
+        // Buttons don't report modifiers, so track Ctrl ourselves for Ctrl-click
+        iced::Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => Some(Message::ModifiersChanged(modifiers)),
+        _ => None,
+    }
+}
+
+// Map global key presses to messages
+// on_key_press only sees keys no widget captured, so a focused text input
+// (e.g. the process search box) swallows these and typing stays safe
+fn keyboard_shortcut(key: keyboard::Key, modifiers: keyboard::Modifiers) -> Option<Message> {
+    match key.as_ref() {
+        keyboard::Key::Character(c) if modifiers.control() && c.eq_ignore_ascii_case("d") => {
+            Some(Message::ToggleTheme)  // Ctrl+D - dark/light
+        }
+        keyboard::Key::Named(keyboard::key::Named::Escape) => Some(Message::ExitMaximizedList),
+        _ => None,
+    }
+}
+
+// Lock a shared System, even if a refresh thread panicked while holding it
+fn lock_system(sys: &Mutex<System>) -> MutexGuard<'_, System> {
+    sys.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// Re-list processes on a blocking thread so a machine with thousands of processes
+// doesn't freeze input. The two process Systems take turns, so each is refreshed
+// every other time. sysinfo measures a process's CPU time and the global CPU time
+// over the same span (since that System's last process refresh), so the percentage
+// is right - averaged over the last two refreshes. That only holds as long as
+// nothing else calls refresh_cpu() on them; see `stats_sys`
+async fn refresh_processes_in_background(sys: Arc<Mutex<System>>) {
+    let _ = tokio::task::spawn_blocking(move || lock_system(&sys).refresh_processes()).await;
+}
+
+// Usage of every mounted disk
+async fn fetch_disk_usage() -> Vec<DiskInfo> {
+    // Disks (new API: separate Disks struct)
+    let mut disks = Disks::new_with_refreshed_list();
+    disks.refresh();
+
+    disks
+        .list()
+        .iter()
+        .map(|disk| {
+            let t = disk.total_space();
+            let a = disk.available_space();
+            DiskInfo {
+                name: disk.name().to_string_lossy().into_owned(),
+                mount_point: disk.mount_point().display().to_string(),
+                used: t.saturating_sub(a),
+                total: t,
+            }
+        })
+        .collect()
+}
+
+// Per-process network attribution needs platform code; only Linux (/proc) is implemented
+const NET_TALKERS_SUPPORTED: bool = cfg!(all(feature = "net-talkers", target_os = "linux"));
+
+// Top processes by open network sockets, busiest first
+// sysinfo has no per-process network stats, so on Linux we match socket inodes
+// from /proc/<pid>/fd against /proc/net/{tcp,udp}{,6}
+#[cfg(all(feature = "net-talkers", target_os = "linux"))]
+async fn fetch_net_talkers() -> Vec<NetTalker> {
+    use std::collections::HashMap;
+
+    let mut sockets: HashMap<u64, u64> = HashMap::new();
+    for table in ["tcp", "tcp6", "udp", "udp6"] {
+        if let Ok(contents) = fs::read_to_string(format!("/proc/net/{}", table)) {
+            sockets.extend(parse_proc_net_sockets(&contents));
+        }
+    }
+
+    let mut talkers = Vec::new();
+    let Ok(proc_dir) = fs::read_dir("/proc") else {
+        return talkers;
+    };
+    for entry in proc_dir.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse::<usize>().ok()) else {
+            continue;
         };
-        let list_border = if self.dark_mode { 
-            Color::from_rgb(0.4, 0.4, 0.4) 
-        } else { 
-            Color::from_rgb(0.2, 0.2, 0.2) 
+        // Other users' fds aren't readable without privileges; skip those quietly
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
         };
-        
-        let process_container = container(process_list)
-            .padding(15)
-            .style(move |_theme: &Theme| Appearance {
-                text_color: None,
-                background: Some(Background::Color(list_bg)),
-                border: Border { 
-                    color: list_border, 
-                    width: 1.0, 
-                    radius: 4.0.into() 
-                },
-                shadow: Default::default(),
-            });
 
-        content_column = content_column.push(process_container);
+        let mut talker = NetTalker { pid: Pid::from(pid), connections: 0, queued_bytes: 0 };
+        for fd in fds.flatten() {
+            let Ok(link) = fs::read_link(fd.path()) else { continue };
+            if let Some(queued) = socket_inode(&link.to_string_lossy()).and_then(|inode| sockets.get(&inode)) {
+                talker.connections += 1;
+                talker.queued_bytes = talker.queued_bytes.saturating_add(*queued);
+            }
+        }
+        if talker.connections > 0 {
+            talkers.push(talker);
+        }
+    }
 
-        // Show detailed view when a process is selected
-        if let Some(pid) = self.selected {
-            if let Some(proc_) = self.sys.process(pid) {
-                content_column = content_column.push(vertical_space().height(Length::Fixed(15.0)));
-                
-                // Extract the colors outside the closure to avoid lifetime issues
-                let detail_bg = if self.dark_mode { 
-                    Color::from_rgb(0.15, 0.15, 0.15) 
-                } else { 
-                    Color::from_rgb(0.95, 0.95, 0.95) 
-                };
-                let detail_border = if self.dark_mode { 
-                    Color::from_rgb(0.4, 0.4, 0.4) 
-                } else { 
-                    Color::from_rgb(0.2, 0.2, 0.2) 
-                };
-                
-                content_column = content_column.push(
-                    container(
-                        column![
-                            text("Selected Process Details").size(18),
-                            vertical_space().height(Length::Fixed(10.0)),
-                            row![
-                                column![
-                                    text("Name:").size(14).style(Color::from_rgb(0.6, 0.6, 0.6)),
-                                    text("PID:").size(14).style(Color::from_rgb(0.6, 0.6, 0.6)),
-                                    text("Status:").size(14).style(Color::from_rgb(0.6, 0.6, 0.6)),
-                                    text("Run Time:").size(14).style(Color::from_rgb(0.6, 0.6, 0.6)), 
-                                ].spacing(6).width(Length::Fixed(80.0)),
-                                column![
-                                    text(proc_.name()).size(14),
-                                    text(format!("{}", pid)).size(14),
-                                    text(format!("{:?}", proc_.status())).size(14),
-                                    text(format!("{} seconds", proc_.run_time())).size(14),  
-                                ].spacing(6).width(Length::Fill),
-                            ].spacing(8),
-                            vertical_space().height(Length::Fixed(10.0)),
-                            row![
-                                column![
-                                    text("CPU Usage").size(14),
-                                    text(format!("{:.1}%", proc_.cpu_usage())).size(18),
-                                ].spacing(4).align_items(Alignment::Center),
-                                column![
-                                    text("Memory").size(14),
-                                    text(format!("{} MB", proc_.memory() / 1024)).size(18),
-                                ].spacing(4).align_items(Alignment::Center),
-                                column![
-                                    text("Virtual Memory").size(14),
-                                    text(format!("{} MB", proc_.virtual_memory() / 1024)).size(16),
-                                ].spacing(4).align_items(Alignment::Center),
-                            ].spacing(30),
-                            vertical_space().height(Length::Fixed(15.0)),
-                            button("KILL PROCESS")
-                                .on_press(Message::KillProcess)
-                                .padding(12),
-                        ].spacing(12),
-                    )
-                    .padding(20)
-                    .style(move |_theme: &Theme| Appearance {
-                        text_color: None,
-                        background: Some(Background::Color(detail_bg)),
-                        border: Border { 
-                            color: detail_border, 
-                            width: 1.0, 
-                            radius: 8.0.into() 
-                        },
-                        shadow: Default::default(),
-                    }),
-                );
+    talkers.sort_by(|a, b| {
+        b.connections.cmp(&a.connections).then(b.queued_bytes.cmp(&a.queued_bytes))
+    });
+    talkers.truncate(5);
+    talkers
+}
+
+#[cfg(not(all(feature = "net-talkers", target_os = "linux")))]
+async fn fetch_net_talkers() -> Vec<NetTalker> {
+    Vec::new()
+}
+
+// socket inode -> queued bytes (tx_queue + rx_queue) from a /proc/net/{tcp,udp} table
+// Listening TCP sockets (state 0A) aren't traffic, so they're skipped
+#[cfg(all(feature = "net-talkers", target_os = "linux"))]
+fn parse_proc_net_sockets(contents: &str) -> Vec<(u64, u64)> {
+    contents
+        .lines()
+        .skip(1)  // Header
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 || fields[3] == "0A" {
+                return None;
             }
+            let (tx, rx) = fields[4].split_once(':')?;
+            let queued = u64::from_str_radix(tx, 16).ok()?.saturating_add(u64::from_str_radix(rx, 16).ok()?);
+            let inode = fields[9].parse::<u64>().ok()?;
+            (inode != 0).then_some((inode, queued))
+        })
+        .collect()
+}
+
+// "socket:[12345]" -> 12345
+#[cfg(all(feature = "net-talkers", target_os = "linux"))]
+fn socket_inode(link: &str) -> Option<u64> {
+    link.strip_prefix("socket:[")?.strip_suffix(']')?.parse().ok()
+}
+
+// Used/total disk space in bytes, summed over all disks
+fn disk_totals(disks: &[DiskInfo]) -> (u64, u64) {
+    disks.iter().fold((0u64, 0u64), |(used, total), disk| {
+        (used.saturating_add(disk.used), total.saturating_add(disk.total))
+    })
+}
+
+// Order disks for the Disks tab; ties keep mount point order so rows don't jump
+fn sort_disks(disks: &mut [DiskInfo], sort: DiskSort, descending: bool) {
+    disks.sort_by(|a, b| {
+        let ordering = match sort {
+            DiskSort::MountPoint => a.mount_point.cmp(&b.mount_point),
+            DiskSort::Usage => usage_percent(a.used, a.total)
+                .partial_cmp(&usage_percent(b.used, b.total))
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.mount_point.cmp(&b.mount_point)),
+        };
+        if descending { ordering.reverse() } else { ordering }
+    });
+}
+
+// Percentage of `total` that `used` represents, clamped to 0-100.
+// Returns 0.0 when total is 0 (e.g. before the first tick) so we never
+// divide by zero and feed NaN into the bar math.
+fn usage_percent(used: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    let percent = used as f64 / total as f64 * 100.0;
+    if percent.is_finite() { percent.clamp(0.0, 100.0) } else { 0.0 }
+}
+
+
+
+impl LightMon {
+    // Refresh CPU and memory on the long-lived stats System (processes have their own
+    // background refresh). Reusing it (instead of a new System per tick) gives sysinfo
+    // the previous sample it needs for real CPU percentages. These are two small
+    // /proc reads, cheap enough for the UI thread. Returns (cpu %, used mem, total mem).
+    fn refresh_system(&mut self) -> (f32, u64, u64) {
+        self.stats_sys.refresh_cpu();
+        self.stats_sys.refresh_memory();
+
+        (
+            self.stats_sys.global_cpu_info().cpu_usage(),
+            self.stats_sys.used_memory(),
+            self.stats_sys.total_memory(),
+        )
+    }
+
+    // Re-list processes in the background. Enumerating every process is the
+    // expensive part of a refresh, so this runs on its own schedule, only while
+    // the Processes tab is open, and off the UI thread. Every process refresh goes
+    // through here so PID-reuse checks and new-process highlights never miss one.
+    // If one is already running, callers just wait for that one to land
+    fn refresh_processes(&mut self) -> Command<Message> {
+        if self.process_refresh_in_flight {
+            return Command::none();
         }
+        self.process_refresh_in_flight = true;
+        Command::perform(refresh_processes_in_background(Arc::clone(&self.spare_sys)), |()| {
+            Message::ProcessesRefreshed
+        })
+    }
 
-        container(scrollable(content_column))
-            .width(Length::Fill)
-            .height(Length::Fill)
-            .into()
+    // Update everything derived from the process list after a refresh swapped in
+    fn apply_process_refresh(&mut self, now: Instant) {
+        self.check_selected_pid_reuse();
+        self.track_new_processes(now);
+        self.record_selected_cpu();
+        self.multi_selected.retain(|pid| self.sys.process(*pid).is_some());
     }
 
-    // Settings tab - adjust app preferences
-    fn view_settings(&self) -> Element<Message> {
-        let setting_bg = if self.dark_mode { 
-            Color::from_rgb(0.15, 0.15, 0.15) 
-        } else { 
-            Color::from_rgb(0.95, 0.95, 0.95) 
-        };
-        let setting_border = if self.dark_mode { 
-            Color::from_rgb(0.4, 0.4, 0.4) 
-        } else { 
-            Color::from_rgb(0.2, 0.2, 0.2) 
+    // Read the selected process's OS details in the background
+    fn fetch_selected_info(&self) -> Command<Message> {
+        match self.selected {
+            Some(pid) => Command::perform(fetch_selected_info(pid), move |info| Message::SelectedInfo(pid, info)),
+            None => Command::none(),
+        }
+    }
+
+    // Update network throughput from the bytes moved since the last snapshot
+    // Uses the real time between snapshots, not refresh_interval, so changed
+    // intervals or skipped ticks don't skew the numbers
+    fn refresh_network_rates(&mut self, now: Instant) {
+        self.networks.refresh();
+        let (received, transmitted) = self.networks.iter().fold((0u64, 0u64), |(rx, tx), (_, data)| {
+            (rx.saturating_add(data.received()), tx.saturating_add(data.transmitted()))
+        });
+
+        if let Some(previous) = self.last_snapshot {
+            let elapsed = now.saturating_duration_since(previous);
+            self.net_rx_rate = rate(received, elapsed);
+            self.net_tx_rate = rate(transmitted, elapsed);
+        }
+        self.last_snapshot = Some(now);
+    }
+
+    // The OS recycles PIDs. If the selected PID now belongs to a process with a
+    // different name, drop the selection and warn instead of silently showing the new one.
+    fn check_selected_pid_reuse(&mut self) {
+        let (Some(pid), Some(expected)) = (self.selected, self.selected_name.as_deref()) else {
+            return;
         };
-        
-        column![
-            text("Settings").size(28),
-            vertical_space().height(Length::Fixed(15.0)),
-            // Refresh rate setting
-            container(
-                column![
-                    text_input("Update frequency (seconds)", &self.refresh_interval_input)
-                        .on_input(Message::SetRefreshInterval)
-                        .padding(10)
-                        .size(14)
-                        .width(Length::Fixed(200.0)),
-                ].spacing(8)
-            )
-            .padding(15)
+        if let Some(process) = self.sys.process(pid)
+            && process.name() != expected
+        {
+            info!("PID {} changed from {} to {}", pid, expected, process.name());
+            self.show_toast(format!("⚠️ PID {} was reused by a different process", pid));
+            self.selected = None;
+            self.selected_name = None;
+        }
+    }
+
+    // Compare this tick's PIDs against the last one to spot freshly started
+    // processes, and drop highlights that are older than the configured duration
+    fn track_new_processes(&mut self, now: Instant) {
+        let current: HashSet<Pid> = self.sys.processes().keys().copied().collect();
+
+        // The very first tick has nothing to compare against - everything would look new
+        if !self.known_pids.is_empty() && self.new_process_highlight_secs > 0 {
+            for pid in current.difference(&self.known_pids) {
+                self.new_pids.insert(*pid, now);
+            }
+        }
+
+        let highlight_for = Duration::from_secs(self.new_process_highlight_secs);
+        self.new_pids.retain(|pid, seen| current.contains(pid) && now.duration_since(*seen) < highlight_for);
+        self.known_pids = current;
+    }
+
+    // Sample the selected process's CPU for its mini graph. A new selection starts
+    // a fresh history; if the process exits, the graph keeps its last values
+    fn record_selected_cpu(&mut self) {
+        if self.selected_history_pid != self.selected {
+            self.selected_history_pid = self.selected;
+            self.selected_cpu_history.clear();
+            self.selected_exited = false;
+        }
+        let Some(pid) = self.selected else {
+            return;
+        };
+        match self.sys.process(pid) {
+            Some(process) => push_bounded(&mut self.selected_cpu_history, process.cpu_usage(), self.history_len),
+            None => self.selected_exited = true,
+        }
+    }
+
+    // Per-process CPU graph line, only if it belongs to the current selection
+    fn selected_cpu_sparkline(&self) -> String {
+        if self.selected_history_pid != self.selected {
+            return String::new();  // Selection changed since the last tick
+        }
+        sparkline(self.selected_cpu_history.iter(), SPARKLINE_WIDTH)
+    }
+
+    // Built-in or user-listed process that needs an extra confirmation to kill
+    fn is_critical_process(&self, name: &str) -> bool {
+        BUILTIN_CRITICAL_PROCESSES.iter().copied()
+            .chain(self.critical_processes.iter().map(String::as_str))
+            .any(|critical| critical.eq_ignore_ascii_case(name))
+    }
+
+    // Export rows for the multi-selected processes that still exist, by PID
+    fn multi_selected_data(&self) -> Vec<ProcessRow> {
+        let mut rows: Vec<_> = self
+            .get_processes_data()
+            .into_iter()
+            .filter(|row| self.multi_selected.contains(&row.0))
+            .collect();
+        rows.sort_by_key(|row| row.0);
+        rows
+    }
+
+    // The user's explicit choice wins; otherwise do what the OS asks for
+    fn reduce_motion_effective(&self) -> bool {
+        self.reduce_motion.unwrap_or(self.os_reduce_motion)
+    }
+
+    // Kick off the slide-in for the screen we just switched to
+    fn start_transition(&mut self) {
+        if !self.reduce_motion_effective() {
+            self.transition_started = Some(Instant::now());
+        }
+    }
+
+    // Maximize only applies while the Processes tab is showing
+    fn list_maximized_now(&self) -> bool {
+        self.list_maximized && matches!(self.current_screen, Screen::Processes)
+    }
+
+    // Seconds between ticks right now - 5x the configured interval while
+    // unfocused if background throttling is on
+    fn effective_refresh_interval(&self) -> u64 {
+        if self.throttle_when_unfocused && !self.window_focused {
+            self.refresh_interval.saturating_mul(UNFOCUSED_REFRESH_FACTOR)
+        } else {
+            self.refresh_interval
+        }
+    }
+
+    // Replace the popup message; it stays up for TOAST_DURATION from now
+    fn show_toast(&mut self, message: String) {
+        self.toast_message = Some(message);
+        self.toast_shown_at = Some(Instant::now());
+    }
+
+    // Drop the toast once it's been visible long enough. Keyed on when the current
+    // toast appeared, so a newer toast always gets its full time on screen
+    fn expire_toast(&mut self, now: Instant) {
+        if self.toast_shown_at.is_some_and(|shown| now.saturating_duration_since(shown) >= TOAST_DURATION) {
+            self.toast_message = None;
+            self.toast_shown_at = None;
+        }
+    }
+
+    // Saved settings that only take effect on the next launch, by name.
+    // That's only what main() reads before the window exists: the renderer, because
+    // iced reads ICED_BACKEND once when it creates the window. Everything else
+    // (theme, intervals, layout, alerts, autostart...) is applied by its Message
+    // handler right away. A new setting that main() has to read belongs here too
+    fn restart_required(&self) -> Vec<&'static str> {
+        let mut pending = Vec::new();
+        if self.renderer != self.startup_renderer {
+            pending.push("Renderer");
+        }
+        pending
+    }
+
+    // Tell the user a metric crossed its threshold, in the app and optionally on the desktop
+    fn raise_alert(&mut self, metric: &str, value: f32, threshold: f32) -> Command<Message> {
+        let body = format!("{} at {:.0}% (alert at {:.0}%)", metric, value, threshold);
+        info!("Alert: {}", body);
+        self.show_toast(format!("⚠️ {}", body));
+        if !self.notify_on_alerts {
+            return Command::none();
+        }
+        Command::perform(
+            send_desktop_notification("LightMon alert".to_string(), body),
+            Message::NotificationSent,
+        )
+    }
+
+    // Process list interval, following the main one unless set, and throttled the same way
+    fn effective_process_refresh_interval(&self) -> u64 {
+        let interval = if self.process_refresh_interval == 0 {
+            self.refresh_interval
+        } else {
+            self.process_refresh_interval
+        };
+        if self.throttle_when_unfocused && !self.window_focused {
+            interval.saturating_mul(UNFOCUSED_REFRESH_FACTOR)
+        } else {
+            interval
+        }
+    }
+
+    // CPU value for the bar/number: smoothed if enabled, raw otherwise
+    fn displayed_cpu(&self) -> f32 {
+        match self.cpu_smoothed {
+            Some(smoothed) if self.smooth_cpu => smoothed,
+            _ => self.cpu_usage,
+        }
+    }
+
+    // Persist the current settings to this instance's config file
+    fn save_settings(&self) -> Result<(), String> {
+        save_config(&self.config_path, &self.current_config())
+    }
+
+    // Save after a settings change, telling the user if it didn't stick.
+    // Every setter goes through here; use save_settings() only when the
+    // caller needs the result itself
+    fn persist_settings(&mut self) {
+        if let Err(e) = self.save_settings() {
+            self.show_toast(format!("❌ Couldn't save settings: {}", e));
+        }
+    }
+
+    // Snapshot the persisted settings from current state
+    fn current_config(&self) -> AppConfig {
+        AppConfig {
+            refresh_interval: self.refresh_interval,
+            process_refresh_interval: self.process_refresh_interval,
+            dark_mode: self.dark_mode,
+            autostart: self.autostart,
+            start_minimized: self.start_minimized,
+            show_status_bar: self.show_status_bar,
+            auto_export_minutes: self.auto_export_minutes,
+            bar_style: self.bar_style,
+            smooth_cpu: self.smooth_cpu,
+            cpu_smoothing_alpha: self.cpu_smoothing_alpha,
+            row_click_action: self.row_click_action,
+            throttle_when_unfocused: self.throttle_when_unfocused,
+            show_mem_percent: self.show_mem_percent,
+            new_process_highlight_secs: self.new_process_highlight_secs,
+            overview_metrics: self.overview_metrics.clone(),
+            overview_layout: self.overview_layout,
+            idle_threshold: self.idle_threshold,
+            name_max_chars: self.name_max_chars,
+            stats_in_title: self.stats_in_title,
+            history_len: self.history_len,
+            hide_self: self.hide_self,
+            cpu_alert_threshold: self.cpu_alert_threshold,
+            mem_alert_threshold: self.mem_alert_threshold,
+            notify_on_alerts: self.notify_on_alerts,
+            renderer: self.renderer,
+            critical_processes: self.critical_processes.clone(),
+            cpu_as_cores: self.cpu_as_cores,
+            reduce_motion: self.reduce_motion,
+            last_export_dir: self.last_export_dir.clone(),
+            last_seen_version: self.last_seen_version.clone(),
+        }
+    }
+
+    // Apply a loaded config to the running UI, including the text inputs
+    // Autostart is left alone: it reflects what's registered with this OS,
+    // and flipping it should go through enable/disable_autostart()
+    fn apply_config(&mut self, config: AppConfig) {
+        self.refresh_interval = config.refresh_interval;
+        self.refresh_interval_input = config.refresh_interval.to_string();
+        self.process_refresh_interval = config.process_refresh_interval;
+        self.process_refresh_input = config.process_refresh_interval.to_string();
+        self.dark_mode = config.dark_mode;
+        self.start_minimized = config.start_minimized;
+        self.show_status_bar = config.show_status_bar;
+        self.auto_export_minutes = config.auto_export_minutes;
+        self.auto_export_input = config.auto_export_minutes.to_string();
+        self.bar_style = config.bar_style;
+        self.smooth_cpu = config.smooth_cpu;
+        self.cpu_smoothing_alpha = config.cpu_smoothing_alpha;
+        self.cpu_alpha_input = config.cpu_smoothing_alpha.to_string();
+        self.row_click_action = config.row_click_action;
+        self.throttle_when_unfocused = config.throttle_when_unfocused;
+        self.show_mem_percent = config.show_mem_percent;
+        self.new_process_highlight_secs = config.new_process_highlight_secs;
+        self.new_process_highlight_input = config.new_process_highlight_secs.to_string();
+        self.overview_metrics = config.overview_metrics;
+        self.overview_layout = config.overview_layout;
+        self.idle_threshold = config.idle_threshold;
+        self.idle_threshold_input = config.idle_threshold.to_string();
+        self.name_max_chars = config.name_max_chars;
+        self.name_max_chars_input = config.name_max_chars.to_string();
+        self.stats_in_title = config.stats_in_title;
+        self.set_history_len(config.history_len);
+        self.hide_self = config.hide_self;
+        self.cpu_alert_threshold = config.cpu_alert_threshold;
+        self.cpu_alert_input = config.cpu_alert_threshold.to_string();
+        self.mem_alert_threshold = config.mem_alert_threshold;
+        self.mem_alert_input = config.mem_alert_threshold.to_string();
+        self.notify_on_alerts = config.notify_on_alerts;
+        self.renderer = config.renderer;
+        self.critical_processes = config.critical_processes;
+        self.cpu_as_cores = config.cpu_as_cores;
+        self.reduce_motion = config.reduce_motion;
+        self.export_dir_input = export_start_dir(config.last_export_dir.as_deref())
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_default();
+        self.last_export_dir = config.last_export_dir;
+        self.last_seen_version = config.last_seen_version;
+    }
+
+    // Where the next manual export goes, from the folder box
+    fn export_path(&self, file_name: &str) -> PathBuf {
+        PathBuf::from(self.export_dir_input.trim()).join(file_name)
+    }
+
+    // Remember the folder a manual export just went to, for next time
+    fn remember_export_dir(&mut self, exported: &Path) {
+        let dir = exported.parent().filter(|dir| !dir.as_os_str().is_empty()).map(Path::to_path_buf);
+        if dir != self.last_export_dir {
+            self.last_export_dir = dir;
+            self.persist_settings();
+        }
+    }
+
+    // Change the history cap, dropping the oldest samples if it shrank
+    fn set_history_len(&mut self, len: usize) {
+        self.history_len = len;
+        self.history_len_input = len.to_string();
+        for history in [&mut self.cpu_history, &mut self.mem_history, &mut self.selected_cpu_history] {
+            while history.len() > len {
+                history.pop_front();
+            }
+        }
+    }
+
+    // Kill a process using Windows taskkill command
+    // Never kills LightMon itself, so bulk actions can call this safely
+    fn kill_process(&self, pid: Pid) -> Result<(), String> {
+        if self.self_pid == Some(pid) {
+            return Err("that's LightMon itself - close the window instead".into());
+        }
+        let output = ProcessCommand::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/F"])  // /F = force kill
+            .output()
+            .map_err(|e| format!("Failed to run taskkill: {}", e))?;
+        
+        if output.status.success() {
+            info!("Successfully killed process {}", pid);
+            Ok(())
+        } else {
+            let error_msg = String::from_utf8_lossy(&output.stderr);
+            Err(format!("Windows taskkill failed: {}", error_msg))
+        }
+    }
+
+    // Everything we know about one process, formatted for pasting into a bug report
+    // None if the process is gone
+    fn process_details_text(&self, pid: Pid) -> Option<String> {
+        let process = self.sys.process(pid)?;
+        let users = Users::new_with_refreshed_list();
+        let user = process
+            .user_id()
+            .map(|uid| {
+                users
+                    .get_user_by_id(uid)
+                    .map(|u| u.name().to_string())
+                    .unwrap_or_else(|| format!("{:?}", uid))
+            })
+            .unwrap_or_else(|| "N/A".into());
+        let exe = process
+            .exe()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "N/A".into());
+
+        Some(format!(
+            "Name: {}\nPID: {}\nCPU: {:.1}%\nMemory: {}\nVirtual Memory: {}\nStatus: {:?}\nUser: {}\nCommand: {}\nExecutable: {}\nStart Time: {} (Unix time, running for {})\n",
+            process.name(),
+            pid,
+            process.cpu_usage(),
+            format_bytes(process.memory()),
+            format_bytes(process.virtual_memory()),
+            process.status(),
+            user,
+            process.cmd().join(" "),
+            exe,
+            process.start_time(),
+            format_duration(process.run_time()),
+        ))
+    }
+
+    // Plain-text report for bug reports: OS, resource snapshot, config and every process
+    fn build_diagnostics_report(&self) -> String {
+        let unknown = || "unknown".to_string();
+        let mut report = String::new();
+
+        report.push_str("=== LightMon diagnostics ===\n\n");
+
+        report.push_str("[System]\n");
+        report.push_str(&format!("OS: {} {}\n", System::name().unwrap_or_else(unknown), System::os_version().unwrap_or_else(unknown)));
+        report.push_str(&format!("Kernel: {}\n", System::kernel_version().unwrap_or_else(unknown)));
+        report.push_str(&format!("Host: {}\n", System::host_name().unwrap_or_else(unknown)));
+        report.push_str(&format!("Arch: {}\n", std::env::consts::ARCH));
+        report.push_str(&format!("CPU cores: {}\n", self.stats_sys.cpus().len()));
+        report.push_str(&format!("Uptime: {} seconds\n\n", System::uptime()));
+
+        report.push_str("[Snapshot]\n");
+        report.push_str(&format!("CPU: {:.1}%\n", self.cpu_usage));
+        report.push_str(&format!(
+            "Memory: {} / {} ({:.1}%)\n",
+            format_bytes(self.memory_used), format_bytes(self.memory_total),
+            usage_percent(self.memory_used, self.memory_total)
+        ));
+        report.push_str(&format!(
+            "Disk: {} / {} ({:.1}%)\n\n",
+            format_bytes(self.disk_used), format_bytes(self.disk_total),
+            usage_percent(self.disk_used, self.disk_total)
+        ));
+
+        report.push_str("[Config]\n");
+        report.push_str(&toml::to_string(&self.current_config()).unwrap_or_else(|e| format!("<unavailable: {}>\n", e)));
+        report.push('\n');
+
+        let processes = self.get_processes_data();
+        report.push_str(&format!("[Processes] ({})\n", processes.len()));
+        report.push_str(CSV_HEADER);
+        report.push('\n');
+        for row in &processes {
+            report.push_str(&csv_row(row));
+            report.push('\n');
+        }
+
+        report
+    }
+
+    // Get process data for CSV export (and the diagnostics report).
+    // Names go through sanitize_process_name here, at the point they leave the app
+    fn get_processes_data(&self) -> Vec<ProcessRow> {
+        self.sys.processes()
+            .iter()
+            .map(|(pid, process)| {
+                (
+                    *pid,
+                    sanitize_process_name(process.name().as_bytes()),
+                    process.cpu_usage(),
+                    process.memory(),
+                    format!("{:?}", process.status())
+                )
+            })
+            .collect()
+    }
+
+    // One-line CPU/mem/disk summary shown at the bottom of every screen
+    // Reads the same fields as the Overview so both update on each tick
+    fn view_status_bar(&self) -> Element<'_, Message> {
+        let mem_percent = usage_percent(self.memory_used, self.memory_total);
+        let disk_percent = usage_percent(self.disk_used, self.disk_total);
+
+        let bar_bg = card_bg(self.dark_mode);
+        let bar_border = subtle_border(self.dark_mode);
+
+        container(
+            text(format!(
+                "CPU {:.1}%  │  MEM {:.1}%  │  DISK {:.1}%",
+                self.displayed_cpu(), mem_percent, disk_percent
+            ))
+            .size(12),
+        )
+        .width(Length::Fill)
+        .padding([4, 10])
+        .style(move |_theme: &Theme| Appearance {
+            text_color: None,
+            background: Some(Background::Color(bar_bg)),
+            border: Border {
+                color: bar_border,
+                width: 1.0,
+                radius: 2.0.into()
+            },
+            shadow: Default::default(),
+        })
+        .into()
+    }
+
+    // Shown under the nav bar while a saved setting is waiting for a restart
+    fn view_restart_banner(&self, pending: &[&str]) -> Element<'_, Message> {
+        let bg = card_bg(self.dark_mode);
+        let border_color = card_border(self.dark_mode);
+
+        container(
+            row![
+                text(format!("Restart to apply: {}", pending.join(", "))).size(14),
+                horizontal_space(),
+                button("Restart now").on_press(Message::RestartNow).padding([2, 8]),
+            ].align_items(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .padding(10)
+        .style(move |_theme: &Theme| Appearance {
+            text_color: None,
+            background: Some(Background::Color(bg)),
+            border: Border {
+                color: border_color,
+                width: 1.0,
+                radius: 4.0.into()
+            },
+            shadow: Default::default(),
+        })
+        .into()
+    }
+
+    // One-time release notes under the nav bar after an upgrade
+    fn view_whats_new(&self, notes: &[&str]) -> Element<'_, Message> {
+        let bg = card_bg(self.dark_mode);
+        let border_color = card_border(self.dark_mode);
+
+        let mut list = column![
+            row![
+                text(format!("What's new in {}", BETA_TAG)).size(16),
+                horizontal_space(),
+                button("Dismiss").on_press(Message::DismissWhatsNew).padding([2, 8]),
+            ].align_items(Alignment::Center),
+        ].spacing(4);
+        for note in notes {
+            list = list.push(text(format!("• {}", note)).size(13).style(card_text(self.dark_mode)));
+        }
+
+        container(list)
+            .width(Length::Fill)
+            .padding(10)
             .style(move |_theme: &Theme| Appearance {
                 text_color: None,
-                background: Some(Background::Color(setting_bg)),
-                border: Border { 
-                    color: setting_border, 
-                    width: 1.0, 
-                    radius: 4.0.into() 
+                background: Some(Background::Color(bg)),
+                border: Border {
+                    color: border_color,
+                    width: 1.0,
+                    radius: 4.0.into()
                 },
                 shadow: Default::default(),
-            }),
-            vertical_space().height(Length::Fixed(20.0)),
-            // Theme setting
+            })
+            .into()
+    }
+
+    // Overview tab - shows system resource usage
+    fn view_overview(&self) -> Element<'_, Message> {
+        // Calculate percentages and convert units
+        // convert memory KB -> GB
+        let mem_total_gb = (self.memory_total as f64) / 1024.0 / 1024.0;
+        let mem_used_gb  = (self.memory_used as f64)  / 1024.0 / 1024.0;
+        let mem_percent = usage_percent(self.memory_used, self.memory_total);
+
+        let disk_percent = usage_percent(self.disk_used, self.disk_total);
+        let swap_percent = usage_percent(self.stats_sys.used_swap(), self.stats_sys.total_swap());
+
+        // Helper to create consistent stat boxes
+        let bar_style = self.bar_style;
+        let stat_box = |label: &str, percent: f32| {
+            let bg = card_bg(self.dark_mode);
+            let text_color = card_text(self.dark_mode);
+            let border_color = card_border(self.dark_mode);
+
+            // The progress bar - text glyphs or the native widget
+            let bar: Element<Message> = match bar_style.glyphs() {
+                Some((filled, empty)) => text(text_bar(percent as f64, filled, empty)).size(16).into(),
+                None => progress_bar(0.0..=100.0, percent)
+                    .width(Length::Fixed(220.0))
+                    .height(Length::Fixed(14.0))
+                    .into(),
+            };
+
             container(
                 column![
-                    text("Theme").size(16),
-                    vertical_space().height(Length::Fixed(8.0)),
+                    text(label).size(16),
                     row![
-                        button(if self.dark_mode { "Light" } else { "● Light" })
-                            .on_press(Message::ToggleTheme)
-                            .padding(12),
-                        button(if self.dark_mode { "● Dark" } else { "Dark" })
-                            .on_press(Message::ToggleTheme)
-                            .padding(12),
-                    ].spacing(12),
-                ].spacing(8)
+                        bar,
+                        text(format!("{:.1}%", percent)).width(Length::Fixed(70.0)).size(16),  // Percentage
+                    ].spacing(12).align_items(Alignment::Center),
+                ].spacing(6)
             )
-            .padding(15)
+            .padding(14)
             .style(move |_theme: &Theme| Appearance {
-                text_color: None,
-                background: Some(Background::Color(setting_bg)),
+                text_color: Some(text_color),
+                background: Some(Background::Color(bg)),
                 border: Border { 
-                    color: setting_border, 
+                    color: border_color, 
                     width: 1.0, 
                     radius: 4.0.into() 
                 },
                 shadow: Default::default(),
-            }),
+            })
+        };
+
+        // Stat boxes in the user's chosen order; hidden ones are simply not in the list
+        let mut stat_boxes = self.overview_metrics.iter().map(|metric| {
+            let percent = match metric {
+                OverviewMetric::Cpu => self.displayed_cpu(),
+                OverviewMetric::Memory => mem_percent as f32,
+                OverviewMetric::Disk => disk_percent as f32,
+                OverviewMetric::Swap => swap_percent as f32,
+            };
+            stat_box(metric.label(), percent)
+        });
+
+        // Same boxes either way; the grid just puts them side by side
+        let stat_boxes: Element<Message> = match self.overview_layout {
+            OverviewLayout::Stacked => column(stat_boxes.map(Element::from)).spacing(8).into(),
+            OverviewLayout::Grid => {
+                let mut grid = column![].spacing(8);
+                loop {
+                    let line: Vec<Element<Message>> = stat_boxes
+                        .by_ref()
+                        .take(OverviewLayout::GRID_COLUMNS)
+                        .map(|stat| stat.width(Length::FillPortion(1)).into())
+                        .collect();
+                    if line.is_empty() {
+                        break;
+                    }
+                    // Pad a short last line so its box stays the same width as the others
+                    let missing = OverviewLayout::GRID_COLUMNS - line.len();
+                    let mut line = Row::with_children(line).spacing(8);
+                    for _ in 0..missing {
+                        line = line.push(horizontal_space().width(Length::FillPortion(1)));
+                    }
+                    grid = grid.push(line);
+                }
+                grid.into()
+            }
+        };
+
+        // With long intervals the screen can sit still for minutes; say so
+        let interval = self.effective_refresh_interval();
+        let countdown = match seconds_until_refresh(self.last_snapshot, interval, Instant::now()) {
+            Some(secs) if interval > 1 => format!("Refreshing every {}  •  next refresh in {}", format_duration(interval), format_duration(secs)),
+            _ => format!("Refreshing every {}", format_duration(interval)),
+        };
+
+        column![
+            text("System Overview").size(28),
+            text(countdown).size(12).style(label_color(self.dark_mode)),
+            vertical_space().height(Length::Fixed(10.0)),
+            stat_boxes,
+            vertical_space().height(Length::Fixed(15.0)),
+            text(format!("CPU history     {}", sparkline(self.cpu_history.iter(), SPARKLINE_WIDTH))).size(14),
+            text(format!("Memory history  {}", sparkline(self.mem_history.iter(), SPARKLINE_WIDTH))).size(14),
+            text(format!("Memory: {:.1} / {:.1} GB", mem_used_gb, mem_total_gb))
+                .size(14),
+            text(format!(
+                "Network: ↓ {}/s  ↑ {}/s",
+                format_bytes(self.net_rx_rate as u64),
+                format_bytes(self.net_tx_rate as u64)
+            ))
+            .size(14),
+            vertical_space().height(Length::Fixed(10.0)),
+            self.view_net_talkers(),
         ]
-        .spacing(15)
+        .spacing(8)
         .padding(25)
         .align_items(Alignment::Start)
         .into()
     }
-}
 
-// Export process list to CSV file
-async fn export_processes_to_csv(processes: Vec<(Pid, String, f32, u64, String)>) -> Result<(), String> {
-    // Small delay to show the "Exporting..." state
-    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-    
-    // Create the CSV file
-    let mut file = File::create("processes.csv")
-        .map_err(|e| format!("Can't create CSV file: {} - check permissions", e))?;
-    
-    // Write header row
-    writeln!(file, "PID,Name,CPU%,Memory (KB),Status")
-        .map_err(|e| format!("Can't write to CSV: {} - disk may be full", e))?;
+    // Content area, shifted right while a tab switch slides in
+    fn view_transition<'a>(&self, content: Element<'a, Message>) -> Element<'a, Message> {
+        let offset = transition_offset(self.transition_started, Instant::now());
+        container(content)
+            .height(Length::Fill)
+            .padding([0.0, 0.0, 0.0, offset])
+            .into()
+    }
+
+    // Top network talkers, or a note when this platform/build can't attribute traffic
+    fn view_net_talkers(&self) -> Element<'_, Message> {
+        let label_color = label_color(self.dark_mode);
+        let mut talkers = column![text("Top network talkers").size(16)].spacing(4);
+
+        if !NET_TALKERS_SUPPORTED {
+            return talkers
+                .push(text("Per-process network usage is not supported on this platform").size(13).style(label_color))
+                .into();
+        }
+        if self.net_talkers.is_empty() {
+            return talkers
+                .push(text("No network activity found yet").size(13).style(label_color))
+                .into();
+        }
+
+        for talker in &self.net_talkers {
+            let name = self.sys.process(talker.pid).map(|p| p.name()).unwrap_or("?");
+            talkers = talkers.push(
+                text(format!(
+                    "{} ({}) - {} connection{}, {} queued",
+                    name,
+                    talker.pid,
+                    talker.connections,
+                    if talker.connections == 1 { "" } else { "s" },
+                    format_bytes(talker.queued_bytes)
+                ))
+                .size(13),
+            );
+        }
+        talkers.into()
+    }
+
+    // Processes tab - list and manage running processes
+    fn view_processes(&self) -> Element<'_, Message> {
+        let mut content_column = column![
+            text("Running Processes").size(28),
+            vertical_space().height(Length::Fixed(10.0)),
+            // Action buttons
+            row![
+                button("Sort by CPU").on_press(Message::SortByCpu).padding(6),
+                button("Sort by Memory").on_press(Message::SortByMemory).padding(6),
+                if self.show_mem_percent {
+                    button("Sort by Mem %").on_press(Message::SortByMemoryPercent).padding(6)
+                } else {
+                    button("Sort by Mem %").padding(6)  // Disabled while the column is hidden
+                },
+                text_input("Export folder (blank = current folder)", &self.export_dir_input)
+                    .on_input(Message::ExportDirChanged)
+                    .padding(6)
+                    .size(14)
+                    .width(Length::Fixed(240.0)),
+                if self.is_exporting {
+                    button("Exporting...").padding(6)  // Show loading state
+                } else {
+                    button("Export to CSV").on_press(Message::ExportProcesses).padding(6)
+                },
+                button(if self.list_maximized { "Restore layout" } else { "Maximize list" })
+                    .on_press(Message::ToggleMaximizeList)
+                    .padding(6),
+            ].spacing(10),
+            vertical_space().height(Length::Fixed(10.0)),
+            // Search box
+            text_input("Search processes by name or PID", &self.filter_text)
+                .on_input(Message::FilterChanged)
+                .padding(10)
+                .size(15),
+            vertical_space().height(Length::Fixed(10.0)),
+        ].spacing(6).padding(25);
+
+        // Batch actions for Ctrl-clicked rows
+        if !self.multi_selected.is_empty() {
+            content_column = content_column.push(
+                row![
+                    text(format!("{} selected - Export to CSV only includes these", self.multi_selected.len())).size(13),
+                    button("Copy selected").on_press(Message::CopyMultiSelection).padding(6),
+                    button("Clear selection").on_press(Message::ClearMultiSelection).padding(6),
+                ].spacing(10).align_items(Alignment::Center),
+            );
+        }
+
+        // Quiet hint that scheduled exports are happening
+        if let Some(path) = &self.last_auto_export {
+            content_column = content_column.push(
+                // Keep the file name visible even for deep folders
+                text(format!("Last auto-export: {}", truncate_middle(&path.display().to_string(), 80)))
+                    .size(12)
+                    .style(label_color(self.dark_mode)),
+            );
+        }
+
+        // Narrow windows (e.g. a thin sidebar) get a condensed table
+        let narrow = is_narrow(self.window_width);
+
+        // Process list header
+        let header_row = if narrow {
+            row![
+                text("Name / PID").width(Length::Fill).size(15),
+                text(process_cpu_label(self.cpu_as_cores)).width(Length::Fixed(60.0)).size(15),
+            ]
+        } else {
+            let mut header = row![
+                text("PID").width(Length::Fixed(80.0)).size(15),
+                text("Name").width(Length::Fill).size(15),
+                text(process_cpu_label(self.cpu_as_cores)).width(Length::Fixed(80.0)).size(15),
+                text("Memory").width(Length::Fixed(100.0)).size(15),
+            ];
+            if self.show_mem_percent {
+                header = header.push(text("Mem %").width(Length::Fixed(70.0)).size(15));
+            }
+            header
+        };
+        let mut process_list = column![
+            header_row.spacing(12).align_items(Alignment::Center),
+        ].spacing(8);
+
+        // Sort processes based on current setting
+        let mut processes: Vec<_> = self.sys.processes().iter().collect();
+        match self.sort_by {
+            SortBy::Cpu => processes.sort_by(|a, b| {
+                b.1.cpu_usage().partial_cmp(&a.1.cpu_usage()).unwrap_or(std::cmp::Ordering::Equal)
+            }),
+            // Share of total RAM orders exactly like raw memory, since the total is shared
+            SortBy::Memory | SortBy::MemoryPercent => processes.sort_by_key(|p| std::cmp::Reverse(p.1.memory())),
+        }
+
+        // Filter processes based on search text
+        // The selected process always stays visible so the selection never silently disappears
+        let filtered = processes.into_iter().filter(|(pid, p)| {
+            let hidden = self.hide_self && self.self_pid == Some(**pid);
+            self.selected == Some(**pid) || (!hidden && matches_filter(p.name(), **pid, &self.filter_text))
+        });
+
+        // Display a limited number of processes for performance (more when maximized),
+        // plus the selection if it sorted further down
+        let row_count = visible_row_count(self.window_height, self.list_maximized);
+        let mut visible: Vec<_> = filtered.take(row_count).collect();
+        if let Some(pid) = self.selected
+            && !visible.iter().any(|(p, _)| **p == pid)
+            && let Some((pid, process)) = self.sys.processes().get_key_value(&pid)
+        {
+            visible.push((pid, process));
+        }
+
+        // Tell the user when the highlighted row is only shown because it's selected
+        if let Some(pid) = self.selected
+            && let Some(process) = self.sys.process(pid)
+            && !matches_filter(process.name(), pid, &self.filter_text)
+        {
+            process_list = process_list.push(
+                text(format!("Showing selected process {} even though it doesn't match the search", pid))
+                    .size(12)
+                    .style(label_color(self.dark_mode)),
+            );
+        }
+
+        // Long names are cut so rows stay one line; hovering shows the full name
+        let name_cell = |name: &str| -> Element<'_, Message> {
+            if self.name_max_chars == 0 || name.chars().count() <= self.name_max_chars {
+                return text(name).size(14).into();
+            }
+            tooltip(
+                text(truncate_end(name, self.name_max_chars)).size(14),
+                text(name.to_string()).size(12),
+                tooltip::Position::FollowCursor,
+            )
+            .style(iced::theme::Container::Box)
+            .into()
+        };
+
+        for (pid, process) in visible {
+            let row_content = if narrow {
+                row![
+                    column![
+                        name_cell(process.name()),
+                        text(format!("PID {}", pid)).size(11),
+                    ].width(Length::Fill),
+                    text(format_process_cpu(process.cpu_usage(), self.cpu_as_cores)).width(Length::Fixed(60.0)).size(14),
+                ]
+            } else {
+                let mut columns = row![
+                    text(format!("{}", pid)).width(Length::Fixed(80.0)).size(14),
+                    container(name_cell(process.name())).width(Length::Fill),
+                    text(format_process_cpu(process.cpu_usage(), self.cpu_as_cores)).width(Length::Fixed(80.0)).size(14),
+                    text(format!("{} MB", process.memory() / 1024)).width(Length::Fixed(100.0)).size(14),
+                ];
+                if self.show_mem_percent {
+                    // usage_percent guards against memory_total still being 0 before the first tick
+                    let mem_share = usage_percent(process.memory(), self.memory_total);
+                    columns = columns.push(text(format!("{:.1}", mem_share)).width(Length::Fixed(70.0)).size(14));
+                }
+                columns
+            }.spacing(12).align_items(Alignment::Center);
+
+            // Make each row clickable, highlighting the current selection
+            let mut row_button = button(row_content)
+                .on_press(Message::ProcessRowClicked(*pid))
+                .padding(4);
+            if self.selected == Some(*pid) {
+                row_button = row_button.style(iced::theme::Button::Custom(Box::new(SelectedRow)));
+            } else if self.multi_selected.contains(pid) {
+                row_button = row_button.style(iced::theme::Button::Custom(Box::new(MultiSelectedRow)));
+            } else if self.new_pids.contains_key(pid) {
+                row_button = row_button.style(iced::theme::Button::Custom(Box::new(NewProcessRow)));
+            } else if is_idle(process.cpu_usage(), self.idle_threshold) {
+                row_button = row_button.style(iced::theme::Button::Custom(Box::new(IdleRow)));
+            }
+
+            process_list = process_list.push(row_button);
+
+            // Inline summary for the "Expand inline" click action
+            if self.expanded == Some(*pid) {
+                process_list = process_list.push(
+                    container(
+                        text(format!(
+                            "Status: {:?}  •  Memory: {}  •  Virtual: {}  •  Running for {}",
+                            process.status(),
+                            format_bytes(process.memory()),
+                            format_bytes(process.virtual_memory()),
+                            format_duration(process.run_time()),
+                        ))
+                        .size(12),
+                    )
+                    .padding([2, 12]),
+                );
+            }
+        }
+
+        // Style the process list container
+        let list_bg = panel_bg(self.dark_mode);
+        let list_border = panel_border(self.dark_mode);
+        
+        let process_container = container(process_list)
+            .padding(15)
+            .style(move |_theme: &Theme| Appearance {
+                text_color: None,
+                background: Some(Background::Color(list_bg)),
+                border: Border { 
+                    color: list_border, 
+                    width: 1.0, 
+                    radius: 4.0.into() 
+                },
+                shadow: Default::default(),
+            });
+
+        content_column = content_column.push(process_container);
+
+        if !self.list_maximized {
+            content_column = content_column.push(vertical_space().height(Length::Fixed(10.0)));
+            content_column = content_column.push(self.view_parent_groups());
+        }
+
+        // Show detailed view when a process is selected (not while the list is maximized)
+        if !self.list_maximized
+            && let Some(pid) = self.selected
+            && let Some(proc_) = self.sys.process(pid)
+        {
+            content_column = content_column.push(vertical_space().height(Length::Fixed(15.0)));
+
+            // Read in the background; None until the first reading for this selection lands
+            let info = self.selected_info
+                .filter(|(read_pid, _)| *read_pid == pid)
+                .map(|(_, info)| info);
+            
+            // Resident / shared / virtual memory, one labeled row each
+            // Shared memory is only known on some platforms, so that row is optional
+            let label_color = label_color(self.dark_mode);
+            let memory_row = |label: &'static str, bytes: u64, hint: &'static str| {
+                row![
+                    text(label).size(14).style(label_color).width(Length::Fixed(80.0)),
+                    text(format_bytes(bytes)).size(14).width(Length::Fixed(100.0)),
+                    text(hint).size(12).style(label_color),
+                ].spacing(8).align_items(Alignment::Center)
+            };
+            let mut memory_grid = column![
+                memory_row("Resident:", proc_.memory(), "Physical RAM in use right now"),
+            ].spacing(6);
+            if let Some(shared) = info.and_then(|info| info.shared_memory) {
+                memory_grid = memory_grid.push(
+                    memory_row("Shared:", shared, "Part of resident that's shared with other processes"),
+                );
+            }
+            memory_grid = memory_grid.push(
+                memory_row("Virtual:", proc_.virtual_memory(), "Address space reserved, including unused pages"),
+            );
+
+            // Extract the colors outside the closure to avoid lifetime issues
+            let detail_bg = panel_bg(self.dark_mode);
+            let detail_border = panel_border(self.dark_mode);
+            
+            content_column = content_column.push(
+                container(
+                    column![
+                        text("Selected Process Details").size(18),
+                        vertical_space().height(Length::Fixed(10.0)),
+                        row![
+                            column![
+                                text("Name:").size(14).style(label_color),
+                                text("PID:").size(14).style(label_color),
+                                text("Status:").size(14).style(label_color),
+                                text("Run Time:").size(14).style(label_color),
+                                text("Priority:").size(14).style(label_color),
+                                text("Open handles:").size(14).style(label_color),
+                            ].spacing(6).width(Length::Fixed(100.0)),
+                            column![
+                                text(proc_.name()).size(14),
+                                text(format!("{}", pid)).size(14),
+                                text(format!("{:?}", proc_.status())).size(14),
+                                text(format_duration(proc_.run_time())).size(14),  
+                                text(match info {
+                                    Some(SelectedProcessInfo { nice: Some(nice), .. }) => format!("nice {}", nice),
+                                    Some(_) => "n/a".to_string(),
+                                    None => "…".to_string(),  // Still being read
+                                }).size(14),
+                                text(match info {
+                                    Some(SelectedProcessInfo { open_handles: Some(count), .. }) => count.to_string(),
+                                    Some(_) => "N/A".to_string(),
+                                    None => "…".to_string(),
+                                }).size(14),
+                            ].spacing(6).width(Length::Fill),
+                        ].spacing(8),
+                        vertical_space().height(Length::Fixed(10.0)),
+                        row![
+                            column![
+                                text("CPU Usage").size(14),
+                                text(if self.cpu_as_cores {
+                                    format!("{} cores", format_process_cpu(proc_.cpu_usage(), true))
+                                } else {
+                                    format!("{}%", format_process_cpu(proc_.cpu_usage(), false))
+                                }).size(18),
+                            ].spacing(4).align_items(Alignment::Center),
+                            column![
+                                text("Memory").size(14),
+                                text(format_bytes(proc_.memory())).size(18),
+                            ].spacing(4).align_items(Alignment::Center),
+                        ].spacing(30),
+                        text(format!("CPU history  {}", self.selected_cpu_sparkline())).size(14),
+                        vertical_space().height(Length::Fixed(10.0)),
+                        text("Memory Breakdown").size(14),
+                        memory_grid,
+                        vertical_space().height(Length::Fixed(15.0)),
+                        row![
+                            button("Copy details")
+                                .on_press(Message::CopyProcessDetails)
+                                .padding(8),
+                            button("Export details")
+                                .on_press(Message::ExportProcessDetails)
+                                .padding(8),
+                        ].spacing(10),
+                        self.view_renice_controls(),
+                        if self.pending_critical_kill == Some(pid) {
+                            column![
+                                text("⚠️ This is a critical system process — are you sure? Killing it can crash your session or the whole system.")
+                                    .size(14)
+                                    .style(error_color(self.dark_mode)),
+                                row![
+                                    button("Yes, kill it").on_press(Message::KillProcess).padding(12),
+                                    button("Cancel").on_press(Message::CancelKill).padding(12),
+                                ].spacing(10),
+                            ].spacing(8)
+                        } else {
+                            column![
+                                button("KILL PROCESS")
+                                    .on_press(Message::KillProcess)
+                                    .padding(12),
+                            ]
+                        },
+                    ].spacing(12),
+                )
+                .padding(20)
+                .style(move |_theme: &Theme| Appearance {
+                    text_color: None,
+                    background: Some(Background::Color(detail_bg)),
+                    border: Border { 
+                        color: detail_border, 
+                        width: 1.0, 
+                        radius: 8.0.into() 
+                    },
+                    shadow: Default::default(),
+                }),
+            );
+        }
+
+        // The selected process exited - keep its last CPU graph around for context
+        if !self.list_maximized
+            && let Some(pid) = self.selected
+            && self.selected_exited
+            && self.selected_history_pid == Some(pid)
+        {
+            let name = self.selected_name.as_deref().unwrap_or("?");
+            content_column = content_column.push(vertical_space().height(Length::Fixed(15.0)));
+            content_column = content_column.push(
+                column![
+                    text(format!("{} ({}) exited", name, pid)).size(16),
+                    text(format!("CPU history  {}", self.selected_cpu_sparkline())).size(14),
+                ]
+                .spacing(6),
+            );
+        }
+
+        if !self.list_maximized {
+            content_column = content_column.push(vertical_space().height(Length::Fixed(15.0)));
+            content_column = content_column.push(self.view_snapshot_compare());
+        }
+
+        container(scrollable(content_column))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    // Nice value editor for the selected process (Unix only), with a warning
+    fn view_renice_controls(&self) -> Element<'_, Message> {
+        if !RENICE_SUPPORTED {
+            return column![].into();
+        }
+        column![
+            row![
+                text_input("Nice (-20 to 19)", &self.nice_input)
+                    .on_input(Message::NiceInputChanged)
+                    .on_submit(Message::ReniceProcess)
+                    .padding(8)
+                    .size(14)
+                    .width(Length::Fixed(140.0)),
+                button("Set priority").on_press(Message::ReniceProcess).padding(8),
+            ].spacing(10).align_items(Alignment::Center),
+            text("⚠️ Advanced: deprioritizing system or critical processes can make your machine sluggish or unresponsive")
+                .size(12)
+                .style(label_color(self.dark_mode)),
+        ]
+        .spacing(6)
+        .into()
+    }
+
+    // Collapsible "usage by parent process" summary, top groups only
+    fn view_parent_groups(&self) -> Element<'_, Message> {
+        const MAX_GROUPS: usize = 10;
+        let arrow = if self.show_parent_groups { "▼" } else { "▶" };
+        let mut section = column![
+            button(text(format!("{} Usage by parent process", arrow)).size(16))
+                .on_press(Message::ToggleParentGroups)
+                .padding(4),
+        ].spacing(6);
+        if !self.show_parent_groups {
+            return section.into();
+        }
+
+        let name_of = |pid: Pid| self.sys.process(pid).map(|p| p.name()).unwrap_or("(exited)");
+        let processes: Vec<_> = self.sys.processes()
+            .iter()
+            .map(|(pid, p)| (*pid, p.parent(), p.cpu_usage(), p.memory()))
+            .collect();
+
+        for group in group_by_parent(&processes).into_iter().take(MAX_GROUPS) {
+            let expanded = self.expanded_groups.contains(&group.parent);
+            section = section.push(
+                button(
+                    row![
+                        text(format!("{} {} ({})", if expanded { "▼" } else { "▶" }, name_of(group.parent), group.parent))
+                            .width(Length::Fill)
+                            .size(14),
+                        text(format!("{} children", group.children.len())).width(Length::Fixed(100.0)).size(14),
+                        text(format_process_cpu(group.cpu, self.cpu_as_cores)).width(Length::Fixed(80.0)).size(14),
+                        text(format_bytes(group.memory)).width(Length::Fixed(100.0)).size(14),
+                    ].spacing(12),
+                )
+                .on_press(Message::ToggleParentGroup(group.parent))
+                .padding(4),
+            );
+            if expanded {
+                for child in &group.children {
+                    section = section.push(
+                        text(format!("    {} ({})", name_of(*child), child))
+                            .size(12)
+                            .style(label_color(self.dark_mode)),
+                    );
+                }
+            }
+        }
+        section.into()
+    }
+
+    // Before/after comparison of two exported CSVs
+    fn view_snapshot_compare(&self) -> Element<'_, Message> {
+        let label_color = label_color(self.dark_mode);
+        let mut section = column![
+            text("Compare snapshots").size(18),
+            text("Load two exported CSVs to see which processes appeared, disappeared or changed a lot")
+                .size(12)
+                .style(label_color),
+            row![
+                text_input("Before (e.g. processes_1718000000.csv)", &self.snapshot_before_input)
+                    .on_input(Message::SnapshotBeforeChanged)
+                    .padding(8)
+                    .size(14),
+                text_input("After", &self.snapshot_after_input)
+                    .on_input(Message::SnapshotAfterChanged)
+                    .padding(8)
+                    .size(14),
+                button("Compare")
+                    .on_press(Message::CompareSnapshots(
+                        PathBuf::from(self.snapshot_before_input.trim()),
+                        PathBuf::from(self.snapshot_after_input.trim()),
+                    ))
+                    .padding(8),
+            ].spacing(8).align_items(Alignment::Center),
+        ].spacing(8);
+
+        let Some(rows) = &self.snapshot_diff else {
+            return section.into();
+        };
+        if rows.is_empty() {
+            return section.push(text("No differences found").size(13).style(label_color)).into();
+        }
+
+        // "12.0 → 30.5" style cells, with "-" for the missing side
+        let pair = |before: Option<String>, after: Option<String>| {
+            format!("{} → {}", before.unwrap_or_else(|| "-".into()), after.unwrap_or_else(|| "-".into()))
+        };
+        section = section.push(
+            row![
+                text("Change").width(Length::Fixed(90.0)).size(14),
+                text("PID").width(Length::Fixed(70.0)).size(14),
+                text("Name").width(Length::Fill).size(14),
+                text("CPU%").width(Length::Fixed(120.0)).size(14),
+                text("Memory").width(Length::Fixed(180.0)).size(14),
+            ].spacing(12),
+        );
+        for diff in rows.iter().take(SNAPSHOT_DIFF_MAX_ROWS) {
+            section = section.push(
+                row![
+                    text(diff.kind.label()).width(Length::Fixed(90.0)).size(13),
+                    text(diff.pid.to_string()).width(Length::Fixed(70.0)).size(13),
+                    text(&diff.name).width(Length::Fill).size(13),
+                    text(pair(diff.cpu.0.map(|c| format!("{:.1}", c)), diff.cpu.1.map(|c| format!("{:.1}", c))))
+                        .width(Length::Fixed(120.0))
+                        .size(13),
+                    text(pair(diff.memory.0.map(format_bytes), diff.memory.1.map(format_bytes)))
+                        .width(Length::Fixed(180.0))
+                        .size(13),
+                ].spacing(12),
+            );
+        }
+        if rows.len() > SNAPSHOT_DIFF_MAX_ROWS {
+            section = section.push(
+                text(format!("…and {} more", rows.len() - SNAPSHOT_DIFF_MAX_ROWS)).size(12).style(label_color),
+            );
+        }
+        section.into()
+    }
+
+    // Disks tab - usage of each mounted volume
+    fn view_disks(&self) -> Element<'_, Message> {
+        let mut disks = self.disks.clone();
+        sort_disks(&mut disks, self.disk_sort, self.disk_sort_descending);
+
+        // Mark the active sort like the theme buttons do
+        let sort_label = |label: &str, sort: DiskSort| {
+            if self.disk_sort == sort { format!("● {}", label) } else { label.to_string() }
+        };
+
+        let mut content_column = column![
+            text("Disks").size(28),
+            vertical_space().height(Length::Fixed(10.0)),
+            row![
+                button(text(sort_label("Sort by name", DiskSort::MountPoint)))
+                    .on_press(Message::SortDisks(DiskSort::MountPoint))
+                    .padding(6),
+                button(text(sort_label("Sort by usage", DiskSort::Usage)))
+                    .on_press(Message::SortDisks(DiskSort::Usage))
+                    .padding(6),
+                button(if self.disk_sort_descending { "Descending ↓" } else { "Ascending ↑" })
+                    .on_press(Message::ToggleDiskSortOrder)
+                    .padding(6),
+            ].spacing(10),
+            vertical_space().height(Length::Fixed(10.0)),
+        ].spacing(6).padding(25);
+
+        let mut disk_list = column![
+            row![
+                text("Mount").width(Length::Fill).size(15),
+                text("Usage").width(Length::Fixed(260.0)).size(15),
+                text("Used / Total").width(Length::Fixed(170.0)).size(15),
+            ].spacing(12).align_items(Alignment::Center),
+        ].spacing(8);
+
+        if disks.is_empty() {
+            disk_list = disk_list.push(text("No disks found yet").size(14));
+        }
+
+        for disk in &disks {
+            let percent = usage_percent(disk.used, disk.total);
+            let bar: Element<Message> = match self.bar_style.glyphs() {
+                Some((filled, empty)) => text(format!("{} {:.1}%", text_bar(percent, filled, empty), percent))
+                    .size(14)
+                    .into(),
+                None => row![
+                    progress_bar(0.0..=100.0, percent as f32)
+                        .width(Length::Fixed(180.0))
+                        .height(Length::Fixed(12.0)),
+                    text(format!("{:.1}%", percent)).size(14),
+                ].spacing(8).align_items(Alignment::Center).into(),
+            };
+
+            disk_list = disk_list.push(
+                row![
+                    column![
+                        text(&disk.mount_point).size(14),
+                        text(&disk.name).size(11),
+                    ].width(Length::Fill),
+                    container(bar).width(Length::Fixed(260.0)),
+                    text(format!("{} / {}", format_bytes(disk.used), format_bytes(disk.total)))
+                        .width(Length::Fixed(170.0))
+                        .size(14),
+                ].spacing(12).align_items(Alignment::Center),
+            );
+        }
+
+        let list_bg = panel_bg(self.dark_mode);
+        let list_border = panel_border(self.dark_mode);
+
+        content_column = content_column.push(
+            container(disk_list)
+                .padding(15)
+                .style(move |_theme: &Theme| Appearance {
+                    text_color: None,
+                    background: Some(Background::Color(list_bg)),
+                    border: Border { 
+                        color: list_border, 
+                        width: 1.0, 
+                        radius: 4.0.into() 
+                    },
+                    shadow: Default::default(),
+                }),
+        );
+
+        container(scrollable(content_column))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    // Checkbox + up/down row per Overview metric: visible ones first in display
+    // order, then the hidden ones
+    fn view_overview_metric_settings(&self) -> Element<'_, Message> {
+        let hidden = OverviewMetric::ALL.into_iter().filter(|m| !self.overview_metrics.contains(m));
+        let rows = self.overview_metrics.iter().copied().chain(hidden).map(|metric| {
+            let visible = self.overview_metrics.contains(&metric);
+            let is_first = self.overview_metrics.first() == Some(&metric);
+            let is_last = self.overview_metrics.last() == Some(&metric);
+
+            // Arrows only work on visible boxes that can actually move
+            let mut up = button("↑").padding([2, 8]);
+            if visible && !is_first {
+                up = up.on_press(Message::MoveOverviewMetricUp(metric));
+            }
+            let mut down = button("↓").padding([2, 8]);
+            if visible && !is_last {
+                down = down.on_press(Message::MoveOverviewMetricDown(metric));
+            }
+
+            row![
+                checkbox(metric.label(), visible)
+                    .on_toggle(move |show| Message::ToggleOverviewMetric(metric, show))
+                    .width(Length::Fixed(120.0)),
+                up,
+                down,
+            ]
+            .spacing(6)
+            .align_items(Alignment::Center)
+            .into()
+        });
+        column(rows).spacing(6).into()
+    }
+
+    // Built-in safeguard list (read-only) plus the user's own additions
+    fn view_critical_process_settings(&self) -> Element<'_, Message> {
+        let mut list = column![
+            text("Killing these asks for an extra confirmation:").size(14),
+            text(BUILTIN_CRITICAL_PROCESSES.join(", "))
+                .size(12)
+                .style(label_color(self.dark_mode)),
+            row![
+                text_input("Process name, e.g. postgres", &self.critical_name_input)
+                    .on_input(Message::CriticalNameInputChanged)
+                    .on_submit(Message::AddCriticalProcess)
+                    .padding(8)
+                    .size(14)
+                    .width(Length::Fixed(260.0)),
+                button("Add").on_press(Message::AddCriticalProcess).padding(8),
+            ].spacing(10).align_items(Alignment::Center),
+        ].spacing(8);
+
+        for name in &self.critical_processes {
+            list = list.push(
+                row![
+                    text(name).size(14).width(Length::Fixed(200.0)),
+                    button("Remove").on_press(Message::RemoveCriticalProcess(name.clone())).padding([2, 8]),
+                ].spacing(10).align_items(Alignment::Center),
+            );
+        }
+        list.into()
+    }
+
+    // Settings tab - adjust app preferences
+    fn view_settings(&self) -> Element<'_, Message> {
+        let setting_bg = panel_bg(self.dark_mode);
+        let setting_border = panel_border(self.dark_mode);
+        
+        // Each section is (title, other words people might search for, contents).
+        // The search box matches against the first two, so keep the keywords in
+        // step with the labels inside the section
+        let sections: Vec<(&str, &str, Element<'_, Message>)> = vec![
+            (
+                "Update frequency",
+                "refresh interval seconds process list",
+                column![
+                    validated_number_input(
+                        "Update frequency (seconds)",
+                        &self.refresh_interval_input,
+                        REFRESH_INTERVAL_RANGE,
+                        Message::SetRefreshInterval,
+                        200.0,
+                        self.dark_mode,
+                    ),
+                    row![
+                        text("Process list refresh (seconds, 0 = same as above)").size(14),
+                        validated_number_input(
+                            "0",
+                            &self.process_refresh_input,
+                            PROCESS_REFRESH_RANGE,
+                            Message::SetProcessRefreshInterval,
+                            80.0,
+                            self.dark_mode,
+                        ),
+                    ].spacing(10).align_items(Alignment::Center),
+                ].spacing(8).into(),
+            ),
+            (
+                "Theme",
+                "dark light mode colors",
+                row![
+                    button(if self.dark_mode { "Light" } else { "● Light" })
+                        .on_press(Message::ToggleTheme)
+                        .padding(12),
+                    button(if self.dark_mode { "● Dark" } else { "Dark" })
+                        .on_press(Message::ToggleTheme)
+                        .padding(12),
+                ].spacing(12).into(),
+            ),
+            (
+                "Startup",
+                "login autostart minimized boot",
+                column![
+                    checkbox("Start LightMon at login", self.autostart)
+                        .on_toggle(Message::ToggleAutostart),
+                    checkbox("Start minimized", self.start_minimized)
+                        .on_toggle(Message::ToggleStartMinimized),
+                ].spacing(8).into(),
+            ),
+            (
+                "Display",
+                "status bar window title reduce motion animation focused throttle smooth cpu smoothing alpha history graph samples bar style",
+                column![
+                    checkbox("Show status bar", self.show_status_bar)
+                        .on_toggle(Message::ToggleStatusBar),
+                    checkbox("Show CPU and memory in the window title", self.stats_in_title)
+                        .on_toggle(Message::ToggleStatsInTitle),
+                    checkbox(
+                        if self.reduce_motion.is_none() {
+                            "Reduce motion (no tab animations) - following your system setting"
+                        } else {
+                            "Reduce motion (no tab animations)"
+                        },
+                        self.reduce_motion_effective(),
+                    )
+                    .on_toggle(Message::ToggleReduceMotion),
+                    checkbox(
+                        format!("Refresh {}x slower when the window isn't focused", UNFOCUSED_REFRESH_FACTOR),
+                        self.throttle_when_unfocused,
+                    )
+                    .on_toggle(Message::ToggleThrottleUnfocused),
+                    checkbox("Smooth CPU readings", self.smooth_cpu)
+                        .on_toggle(Message::ToggleCpuSmoothing),
+                    validated_number_input(
+                        "Smoothing alpha",
+                        &self.cpu_alpha_input,
+                        CPU_ALPHA_RANGE,
+                        Message::SetCpuSmoothingAlpha,
+                        200.0,
+                        self.dark_mode,
+                    ),
+                    text("History length (samples kept for the Overview graphs)").size(14),
+                    validated_number_input(
+                        "Samples",
+                        &self.history_len_input,
+                        HISTORY_LEN_RANGE,
+                        Message::SetHistoryLen,
+                        200.0,
+                        self.dark_mode,
+                    ),
+                    text("Bar style").size(14),
+                    Row::with_children(BarStyle::ALL.into_iter().map(|style| {
+                        let label = if style == self.bar_style {
+                            format!("● {}", style.label())
+                        } else {
+                            style.label().to_string()
+                        };
+                        button(text(label)).on_press(Message::SetBarStyle(style)).padding(8).into()
+                    })).spacing(8),
+                ].spacing(8).into(),
+            ),
+            (
+                "Overview",
+                "stat boxes metrics order cpu memory disk swap layout stacked grid dashboard",
+                column![
+                    text("Layout").size(14),
+                    Row::with_children(OverviewLayout::ALL.into_iter().map(|layout| {
+                        let label = if layout == self.overview_layout {
+                            format!("● {}", layout.label())
+                        } else {
+                            layout.label().to_string()
+                        };
+                        button(text(label)).on_press(Message::SetOverviewLayout(layout)).padding(8).into()
+                    })).spacing(8),
+                    self.view_overview_metric_settings(),
+                ].spacing(8).into(),
+            ),
+            (
+                "Process list",
+                "mem % column cpu cores percent hide self lightmon new processes highlight idle dim threshold name length truncate ellipsis click row action copy pid expand",
+                column![
+                    checkbox("Show Mem % column (share of total RAM)", self.show_mem_percent)
+                        .on_toggle(Message::ToggleMemPercentColumn),
+                    checkbox("Hide LightMon itself", self.hide_self)
+                        .on_toggle(Message::ToggleHideSelf),
+                    checkbox("Show CPU as cores used (1.30) instead of percent (130%)", self.cpu_as_cores)
+                        .on_toggle(Message::ToggleCpuAsCores),
+                    text("Highlight new processes for (seconds, 0 = off)").size(14),
+                    validated_number_input(
+                        "Seconds",
+                        &self.new_process_highlight_input,
+                        NEW_PROCESS_HIGHLIGHT_RANGE,
+                        Message::SetNewProcessHighlight,
+                        200.0,
+                        self.dark_mode,
+                    ),
+                    text("Dim processes using less CPU% than (0 = off)").size(14),
+                    validated_number_input(
+                        "CPU %",
+                        &self.idle_threshold_input,
+                        IDLE_THRESHOLD_RANGE,
+                        Message::SetIdleThreshold,
+                        200.0,
+                        self.dark_mode,
+                    ),
+                    text("Cut process names longer than (characters, 0 = off)").size(14),
+                    validated_number_input(
+                        "Characters",
+                        &self.name_max_chars_input,
+                        NAME_MAX_CHARS_RANGE,
+                        Message::SetNameMaxChars,
+                        200.0,
+                        self.dark_mode,
+                    ),
+                    text("Clicking a row").size(14),
+                    Row::with_children(RowClickAction::ALL.into_iter().map(|action| {
+                        let label = if action == self.row_click_action {
+                            format!("● {}", action.label())
+                        } else {
+                            action.label().to_string()
+                        };
+                        button(text(label)).on_press(Message::SetRowClickAction(action)).padding(8).into()
+                    })).spacing(8),
+                ].spacing(8).into(),
+            ),
+            (
+                "Auto-export",
+                "scheduled csv export minutes timestamp",
+                column![
+                    validated_number_input(
+                        "Export every N minutes (0 = off)",
+                        &self.auto_export_input,
+                        AUTO_EXPORT_RANGE,
+                        Message::SetAutoExportInterval,
+                        260.0,
+                        self.dark_mode,
+                    ),
+                    text("Writes processes_<timestamp>.csv to the current folder")
+                        .size(12)
+                        .style(label_color(self.dark_mode)),
+                ].spacing(8).into(),
+            ),
+            (
+                "Alerts",
+                "alert threshold cpu memory notification notify desktop warning",
+                column![
+                    text("Alert when CPU reaches (%, 0 = off)").size(14),
+                    validated_number_input(
+                        "CPU %",
+                        &self.cpu_alert_input,
+                        ALERT_THRESHOLD_RANGE,
+                        Message::SetCpuAlertThreshold,
+                        200.0,
+                        self.dark_mode,
+                    ),
+                    text("Alert when memory use reaches (%, 0 = off)").size(14),
+                    validated_number_input(
+                        "Memory %",
+                        &self.mem_alert_input,
+                        ALERT_THRESHOLD_RANGE,
+                        Message::SetMemAlertThreshold,
+                        200.0,
+                        self.dark_mode,
+                    ),
+                    checkbox("Notify on alerts (desktop notification, also when LightMon is in the background)", self.notify_on_alerts)
+                        .on_toggle(Message::ToggleNotifyOnAlerts),
+                ].spacing(8).into(),
+            ),
+            (
+                "Kill safeguards",
+                "critical processes confirm kill protect system",
+                self.view_critical_process_settings(),
+            ),
+            (
+                "Backup",
+                "settings file export import toml",
+                row![
+                    text_input("Settings file path", &self.settings_path_input)
+                        .on_input(Message::SettingsPathChanged)
+                        .padding(10)
+                        .size(14)
+                        .width(Length::Fixed(300.0)),
+                    button("Export settings")
+                        .on_press(Message::ExportSettings)
+                        .padding(10),
+                    button("Import settings")
+                        .on_press(Message::ImportSettings)
+                        .padding(10),
+                ].spacing(12).align_items(Alignment::Center).into(),
+            ),
+            (
+                "Diagnostics",
+                "report bug dump file renderer gpu wgpu software tiny-skia blank window restart",
+                column![
+                    row![
+                        text_input("Report file path", &self.diagnostics_path_input)
+                            .on_input(Message::DiagnosticsPathChanged)
+                            .padding(10)
+                            .size(14)
+                            .width(Length::Fixed(300.0)),
+                        button("Dump diagnostics")
+                            .on_press(Message::DumpDiagnostics)
+                            .padding(10),
+                    ].spacing(12).align_items(Alignment::Center),
+                    text("Renderer (applies after a restart)").size(14),
+                    Row::with_children(RendererChoice::ALL.into_iter().map(|renderer| {
+                        let label = if renderer == self.renderer {
+                            format!("● {}", renderer.label())
+                        } else {
+                            renderer.label().to_string()
+                        };
+                        button(text(label)).on_press(Message::SetRenderer(renderer)).padding(8).into()
+                    })).spacing(8),
+                ].spacing(8).into(),
+            ),
+        ];
+
+        let mut settings = column![
+            text("Settings").size(28),
+            text_input("Search settings", &self.settings_search)
+                .on_input(Message::SettingsSearchChanged)
+                .padding(10)
+                .size(14)
+                .width(Length::Fixed(300.0)),
+            vertical_space().height(Length::Fixed(5.0)),
+        ]
+        .spacing(15)
+        .padding(25)
+        .align_items(Alignment::Start);
+
+        let mut shown = 0;
+        for (title, keywords, body) in sections {
+            if !settings_section_matches(title, keywords, &self.settings_search) {
+                continue;
+            }
+            if shown > 0 {
+                settings = settings.push(vertical_space().height(Length::Fixed(20.0)));
+            }
+            settings = settings.push(
+                container(
+                    column![
+                        text(title).size(16),
+                        vertical_space().height(Length::Fixed(8.0)),
+                        body,
+                    ].spacing(8)
+                )
+                .padding(15)
+                .style(move |_theme: &Theme| Appearance {
+                    text_color: None,
+                    background: Some(Background::Color(setting_bg)),
+                    border: Border { 
+                        color: setting_border, 
+                        width: 1.0, 
+                        radius: 4.0.into() 
+                    },
+                    shadow: Default::default(),
+                }),
+            );
+            shown += 1;
+        }
+        if shown == 0 {
+            settings = settings.push(
+                text(format!("No settings match \"{}\"", self.settings_search.trim()))
+                    .size(14)
+                    .style(label_color(self.dark_mode)),
+            );
+        }
+
+        // Settings keep growing, so let the page scroll
+        scrollable(settings).height(Length::Fill).into()
+    }
+}
+
+// Group processes under their parent: (pid, parent, cpu, memory) in, one group
+// per parent that has at least one child out, busiest CPU first. Only direct
+// children count, so e.g. browser helpers add up under the main browser process
+fn group_by_parent(processes: &[(Pid, Option<Pid>, f32, u64)]) -> Vec<ParentGroup> {
+    let mut groups: HashMap<Pid, ParentGroup> = HashMap::new();
+    for (pid, parent, cpu, memory) in processes {
+        let Some(parent) = parent else { continue };
+        let group = groups.entry(*parent).or_insert_with(|| ParentGroup {
+            parent: *parent,
+            children: Vec::new(),
+            cpu: 0.0,
+            memory: 0,
+        });
+        group.children.push(*pid);
+        group.cpu += cpu;
+        group.memory = group.memory.saturating_add(*memory);
+    }
+
+    // Add the parent's own usage, when it's still around
+    for (pid, _, cpu, memory) in processes {
+        if let Some(group) = groups.get_mut(pid) {
+            group.cpu += cpu;
+            group.memory = group.memory.saturating_add(*memory);
+        }
+    }
+
+    let mut groups: Vec<_> = groups.into_values().collect();
+    for group in &mut groups {
+        group.children.sort();
+    }
+    groups.sort_by(|a, b| b.cpu.total_cmp(&a.cpu).then(b.memory.cmp(&a.memory)).then(a.parent.cmp(&b.parent)));
+    groups
+}
+
+// Export process list to CSV file
+async fn export_processes_to_csv(path: PathBuf, processes: Vec<ProcessRow>) -> Result<PathBuf, String> {
+    // Small delay to show the "Exporting..." state
+    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+    write_processes_csv(&path, &processes).map(|()| path)
+}
+
+// Folder to offer for the next export: the remembered one if it still exists,
+// otherwise the home folder. None (current folder) if nothing was remembered
+fn export_start_dir(saved: Option<&Path>) -> Option<PathBuf> {
+    let saved = saved?;
+    if saved.is_dir() {
+        return Some(saved.to_path_buf());
+    }
+    info!("Last export folder {} is gone, falling back to home", saved.display());
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))  // Windows
+        .map(PathBuf::from)
+}
+
+// Timestamped file name for scheduled exports, e.g. processes_1718000000.csv
+fn auto_export_path(now: std::time::SystemTime) -> PathBuf {
+    let secs = now
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    PathBuf::from(format!("processes_{}.csv", secs))
+}
+
+// Check a config's numbers are within the same ranges the Settings inputs enforce
+fn validate_config(config: &AppConfig) -> Result<(), String> {
+    fn check<T: PartialOrd + std::fmt::Display + Copy>(name: &str, value: T, range: (T, T)) -> Result<(), String> {
+        check_in_range(value, range).map(|_| ()).map_err(|e| format!("{} {}", name, e))
+    }
+    check("refresh_interval", config.refresh_interval, REFRESH_INTERVAL_RANGE)?;
+    check("process_refresh_interval", config.process_refresh_interval, PROCESS_REFRESH_RANGE)?;
+    check("auto_export_minutes", config.auto_export_minutes, AUTO_EXPORT_RANGE)?;
+    check("cpu_smoothing_alpha", config.cpu_smoothing_alpha, CPU_ALPHA_RANGE)?;
+    check("idle_threshold", config.idle_threshold, IDLE_THRESHOLD_RANGE)?;
+    check("name_max_chars", config.name_max_chars, NAME_MAX_CHARS_RANGE)?;
+    check("cpu_alert_threshold", config.cpu_alert_threshold, ALERT_THRESHOLD_RANGE)?;
+    check("mem_alert_threshold", config.mem_alert_threshold, ALERT_THRESHOLD_RANGE)?;
+    check("history_len", config.history_len, HISTORY_LEN_RANGE)?;
+    let unique: HashSet<_> = config.overview_metrics.iter().collect();
+    if unique.len() != config.overview_metrics.len() {
+        return Err("overview_metrics lists the same metric twice".to_string());
+    }
+    Ok(())
+}
+
+// Write the config as TOML for backup/sharing
+async fn export_settings(path: PathBuf, config: AppConfig) -> Result<PathBuf, String> {
+    let config_str = toml::to_string(&config).map_err(|e| e.to_string())?;
+    fs::write(&path, config_str).map_err(|e| format!("{} - check the path and permissions", e))?;
+    Ok(path)
+}
+
+// Read a settings file, rejecting anything that doesn't parse or is out of range
+async fn import_settings(path: PathBuf) -> Result<AppConfig, String> {
+    let config_str = fs::read_to_string(&path).map_err(|e| format!("Can't read {}: {}", path.display(), e))?;
+    let config: AppConfig = toml::from_str(&config_str).map_err(|e| format!("Not a valid settings file: {}", e))?;
+    validate_config(&config)?;
+    Ok(config)
+}
+
+// Write the diagnostics report, returning where it ended up
+async fn write_diagnostics(path: PathBuf, report: String) -> Result<PathBuf, String> {
+    fs::write(&path, report)
+        .map_err(|e| format!("{} - check the path and permissions", e))?;
+    Ok(path)
+}
+
+// Write the CSV to a temp file next to `path`, then rename it into place.
+// The rename is atomic, so quitting mid-export never leaves a truncated CSV behind;
+// on any failure the temp file is removed and the old CSV (if any) is untouched.
+fn write_processes_csv(path: &Path, processes: &[ProcessRow]) -> Result<(), String> {
+    let file_name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let tmp_path = path.with_file_name(format!("{}.tmp", file_name));
+
+    let result = write_csv_rows(&tmp_path, processes).and_then(|()| {
+        fs::rename(&tmp_path, path)
+            .map_err(|e| format!("Can't save CSV file: {} - write failed", e))
+    });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);  // Don't leave partial files around
+    }
+    result
+}
+
+// Process names come from the OS and aren't guaranteed to be valid UTF-8.
+// sysinfo 0.30 already hands us a &str, but exports shouldn't depend on how a
+// given sysinfo version decodes names: invalid bytes become U+FFFD here instead
+// of mojibake or a panic. Commas, quotes and line breaks are left alone -
+// csv_field quotes them on the way out
+fn sanitize_process_name(raw: &[u8]) -> String {
+    String::from_utf8_lossy(raw).into_owned()
+}
+
+// Parse a CSV written by write_csv_rows back into process rows
+fn parse_processes_csv(contents: &str) -> Result<Vec<ProcessRow>, String> {
+    let mut records = parse_csv_records(contents)?.into_iter();
+    match records.next() {
+        Some((_, header)) if header.first().is_some_and(|field| field == "PID") => {}
+        _ => return Err("missing the PID,Name,... header - not a LightMon export".into()),
+    }
+
+    records
+        .map(|(line_no, fields)| {
+            let [pid, name, cpu, memory, status]: [String; 5] = fields
+                .try_into()
+                .map_err(|_| format!("line {}: expected 5 columns", line_no))?;
+            let pid = pid.parse::<usize>().map_err(|_| format!("line {}: bad PID", line_no))?;
+            let cpu = cpu.parse::<f32>().map_err(|_| format!("line {}: bad CPU%", line_no))?;
+            let memory = memory.parse::<u64>().map_err(|_| format!("line {}: bad memory", line_no))?;
+            Ok((Pid::from(pid), name, cpu, memory, status))
+        })
+        .collect()
+}
+
+// Split RFC 4180 CSV into records of fields, each with the (1-based) line it starts on.
+// Quoted fields may contain commas, newlines and doubled quotes; blank lines are skipped
+fn parse_csv_records(contents: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line_no = 1;
+    let mut record_start = 1;
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.is_empty() => in_quotes = true,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes && chars.peek() == Some(&'\n') => {}
+            '\n' if !in_quotes => {
+                fields.push(std::mem::take(&mut field));
+                if fields.len() > 1 || !fields[0].is_empty() {
+                    records.push((record_start, std::mem::take(&mut fields)));
+                }
+                fields.clear();
+                line_no += 1;
+                record_start = line_no;
+            }
+            _ => {
+                if c == '\n' {
+                    line_no += 1;
+                }
+                field.push(c);
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(format!("line {}: unterminated quoted field", record_start));
+    }
+    if !fields.is_empty() || !field.is_empty() {
+        fields.push(field);
+        records.push((record_start, fields));
+    }
+    Ok(records)
+}
+
+// Column names of a process CSV (exports and the diagnostics report)
+const CSV_HEADER: &str = "PID,Name,CPU%,Memory (KB),Status";
+
+// One process as a CSV line, without the line break
+fn csv_row((pid, name, cpu_usage, memory, status): &ProcessRow) -> String {
+    format!("{},{},{:.1},{},{}", pid, csv_field(name), cpu_usage, memory, csv_field(status))
+}
+
+// Quote a CSV field per RFC 4180 when it contains a comma, quote or line break
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
+    }
+}
+
+// A CPU swing of this many percentage points, or memory growing/shrinking by
+// this fraction, counts as a significant change
+const SNAPSHOT_CPU_CHANGE: f32 = 5.0;
+const SNAPSHOT_MEMORY_CHANGE: f64 = 0.25;
+const SNAPSHOT_DIFF_MAX_ROWS: usize = 50;
+
+// Compare two snapshots. Processes are matched on PID *and* name so a
+// reused PID shows up as one gone plus one appeared, not a "change"
+fn diff_snapshots(
+    before: &[ProcessRow],
+    after: &[ProcessRow],
+) -> Vec<SnapshotDiffRow> {
+    let key = |row: &ProcessRow| (row.0, row.1.clone());
+    let before_map: HashMap<_, _> = before.iter().map(|row| (key(row), row)).collect();
+    let after_map: HashMap<_, _> = after.iter().map(|row| (key(row), row)).collect();
+
+    let mut rows = Vec::new();
+    for row in after {
+        match before_map.get(&key(row)) {
+            None => rows.push(SnapshotDiffRow {
+                kind: DiffKind::Appeared,
+                pid: row.0,
+                name: row.1.clone(),
+                cpu: (None, Some(row.2)),
+                memory: (None, Some(row.3)),
+            }),
+            Some(old) => {
+                let cpu_swing = (row.2 - old.2).abs() >= SNAPSHOT_CPU_CHANGE;
+                let mem_base = old.3.max(1) as f64;
+                let mem_swing = (row.3 as f64 - old.3 as f64).abs() / mem_base >= SNAPSHOT_MEMORY_CHANGE;
+                if cpu_swing || mem_swing {
+                    rows.push(SnapshotDiffRow {
+                        kind: DiffKind::Changed,
+                        pid: row.0,
+                        name: row.1.clone(),
+                        cpu: (Some(old.2), Some(row.2)),
+                        memory: (Some(old.3), Some(row.3)),
+                    });
+                }
+            }
+        }
+    }
+    for row in before {
+        if !after_map.contains_key(&key(row)) {
+            rows.push(SnapshotDiffRow {
+                kind: DiffKind::Disappeared,
+                pid: row.0,
+                name: row.1.clone(),
+                cpu: (Some(row.2), None),
+                memory: (Some(row.3), None),
+            });
+        }
+    }
+
+    // Group by kind, then PID, so the table reads predictably
+    rows.sort_by_key(|row| (row.kind as u8, row.pid));
+    rows
+}
+
+// Load and diff two exported CSVs
+async fn compare_snapshots(before: PathBuf, after: PathBuf) -> Result<Vec<SnapshotDiffRow>, String> {
+    let load = |path: &Path| -> Result<Vec<ProcessRow>, String> {
+        let contents = fs::read_to_string(path).map_err(|e| format!("Can't read {}: {}", path.display(), e))?;
+        parse_processes_csv(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+    };
+    Ok(diff_snapshots(&load(&before)?, &load(&after)?))
+}
+
+fn write_csv_rows(path: &Path, processes: &[ProcessRow]) -> Result<(), String> {
+    // Create the CSV file
+    let mut file = File::create(path)
+        .map_err(|e| format!("Can't create CSV file: {} - check permissions", e))?;
+    
+    // Write header row
+    writeln!(file, "{}", CSV_HEADER)
+        .map_err(|e| format!("Can't write to CSV: {} - disk may be full", e))?;
+
+    // Write each process as a row
+    for row in processes {
+        writeln!(file, "{}", csv_row(row))
+            .map_err(|e| format!("Can't write process data: {} - disk error", e))?;
+    }
+
+    // Make sure everything is written to disk before the rename
+    file.flush()
+        .and_then(|()| file.sync_all())
+        .map_err(|e| format!("Can't save CSV file: {} - write failed", e))?;
+
+    Ok(())
+}
+
+// Tests to make sure everything works
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    // Feed a sequence of messages through update(), like the runtime would.
+    // The returned Commands are dropped unrun, so nothing here touches the disk
+    // beyond what update() itself does synchronously
+    fn drive(mon: &mut LightMon, messages: impl IntoIterator<Item = Message>) {
+        for message in messages {
+            let _ = mon.update(message);
+        }
+    }
+
+    // Do what the background process refresh task would, then deliver its result
+    fn finish_process_refresh(mon: &mut LightMon) {
+        assert!(mon.process_refresh_in_flight);
+        lock_system(&mon.spare_sys).refresh_processes();
+        let _ = mon.update(Message::ProcessesRefreshed);
+    }
+
+    // A fresh path under the temp dir, unique to this test run and call
+    fn temp_path(name: &str) -> PathBuf {
+        static NEXT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+        let n = NEXT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        std::env::temp_dir().join(format!("lightmon_test_{}_{}_{}", std::process::id(), n, name))
+    }
+
+    // LightMon with default settings that saves to its own temp file, so tests
+    // neither read nor overwrite the real lightmon_config.toml
+    fn test_mon() -> LightMon {
+        LightMon::with_config(AppConfig::default(), temp_path("config.toml")).0
+    }
+
+    #[tokio::test]
+    async fn test_fetch_system_data_works() {
+        let mut mon = test_mon();
+        let (cpu, used, total) = mon.refresh_system();
+        assert!(cpu >= 0.0);  // CPU should be 0% or more
+        assert!(used <= total);  // Used memory should be <= total memory
+
+        let (disk_used, disk_total) = disk_totals(&fetch_disk_usage().await);
+        assert!(disk_used <= disk_total);
+    }
+
+    #[test]
+    fn test_tick_refreshes_stats_only() {
+        let mut mon = test_mon();
+        let me = sysinfo::get_current_pid().unwrap();
+        let shown_before = mon.sys.processes().len();
+
+        let (_, used, total) = mon.refresh_system();
+        assert_eq!(total, mon.stats_sys.total_memory());
+        assert!(used > 0 && used <= total);
+
+        // CPU/memory come from stats_sys; neither process System is touched or re-listed
+        let _ = mon.update(Message::Tick);
+        assert!(!mon.process_refresh_in_flight);
+        assert_eq!(mon.sys.processes().len(), shown_before);
+        assert!(mon.sys.process(me).is_some());
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config = AppConfig::default();
+        assert_eq!(config.refresh_interval, 1);
+        assert!(!config.dark_mode);
+    }
+
+    #[test]
+    fn test_config_missing_fields_use_defaults() {
+        // Config files written before new settings existed must still load
+        let config: AppConfig = toml::from_str("refresh_interval = 2\ndark_mode = true").unwrap();
+        assert_eq!(config.refresh_interval, 2);
+        assert!(!config.autostart);
+        assert!(!config.start_minimized);
+    }
+
+    #[test]
+    fn test_load_config_no_crash() {
+        let config = load_config(&temp_path("missing.toml"));
+        assert!(config.refresh_interval >= 1);  // Missing file falls back to defaults
+    }
+
+    #[test]
+    fn test_usage_percent_zero_total() {
+        assert_eq!(usage_percent(0, 0), 0.0);  // Before the first tick
+        assert_eq!(usage_percent(512, 0), 0.0);  // No NaN/inf on a zero total
+    }
+
+    #[test]
+    fn test_usage_percent_clamps() {
+        assert_eq!(usage_percent(50, 200), 25.0);
+        assert_eq!(usage_percent(300, 200), 100.0);  // Never above 100%
+    }
+
+    #[test]
+    fn test_cpu_smoothing_keeps_raw_value() {
+        assert_eq!(ema(0.0, 100.0, 0.5), 50.0);
+        assert_eq!(ema(40.0, 80.0, 1.0), 80.0);  // alpha 1 = no smoothing
+        assert_eq!(ema(40.0, 80.0, f32::NAN), 80.0);  // Bad alpha - reseed from the sample
+        assert_eq!(ema(f32::NAN, 80.0, 0.5), 80.0);   // Poisoned average recovers
+        assert_eq!(ema(f32::INFINITY, 80.0, 0.5), 80.0);
+
+        let mut mon = test_mon();
+        mon.smooth_cpu = true;
+        mon.cpu_smoothing_alpha = 0.5;
+        let _ = mon.update(Message::SystemData(0.0, 1, 2, Vec::new()));
+        let _ = mon.update(Message::SystemData(100.0, 1, 2, Vec::new()));
+        assert_eq!(mon.cpu_usage, 100.0);  // Raw sample untouched
+        assert_eq!(mon.displayed_cpu(), 50.0);
+
+        mon.smooth_cpu = false;
+        assert_eq!(mon.displayed_cpu(), 100.0);
+
+        // A NaN alpha from a hand-edited config doesn't stick
+        mon.smooth_cpu = true;
+        mon.cpu_smoothing_alpha = f32::NAN;
+        let _ = mon.update(Message::SystemData(30.0, 1, 2, Vec::new()));
+        mon.cpu_smoothing_alpha = 0.5;
+        let _ = mon.update(Message::SystemData(50.0, 1, 2, Vec::new()));
+        assert_eq!(mon.displayed_cpu(), 40.0);
+    }
+
+    #[test]
+    fn test_seconds_until_refresh() {
+        let start = Instant::now();
+        assert_eq!(seconds_until_refresh(None, 5, start), None);
+        assert_eq!(seconds_until_refresh(Some(start), 3600, start), Some(3600));
+        assert_eq!(seconds_until_refresh(Some(start), 3600, start + Duration::from_millis(1500)), Some(3599));
+        assert_eq!(seconds_until_refresh(Some(start), 5, start + Duration::from_secs(9)), Some(0));  // Overdue
+    }
+
+    #[test]
+    fn test_rate_uses_real_elapsed_time() {
+        assert_eq!(rate(1000, Duration::from_millis(500)), 2000.0);  // Sub-second
+        assert_eq!(rate(9000, Duration::from_secs(3)), 3000.0);  // Multi-second
+        assert_eq!(rate(1000, Duration::ZERO), 0.0);  // No division by zero
+    }
+
+    #[cfg(all(feature = "net-talkers", target_os = "linux"))]
+    #[test]
+    fn test_parse_proc_net_sockets() {
+        let table = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n\
+   0: 0100007F:BC8F 00000000:0000 0A 00000000:00000000 00:00000000 00000000 65534        0 921 1\n\
+   1: 0100007F:8380 0100007F:BC8F 01 00000010:00000001 02:000001D8 00000000     0        0 1114 3\n";
+        // Listening socket 921 skipped, established 1114 has 0x10 + 0x1 bytes queued
+        assert_eq!(parse_proc_net_sockets(table), vec![(1114, 17)]);
+
+        assert_eq!(socket_inode("socket:[1114]"), Some(1114));
+        assert_eq!(socket_inode("pipe:[25411]"), None);
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut history = VecDeque::new();
+        for i in 0..100 {
+            push_bounded(&mut history, i as f32, 60);
+        }
+        assert_eq!(history.len(), 60);
+        assert_eq!(history.front(), Some(&40.0));  // Oldest samples dropped first
+
+        // Shrinking the cap trims existing history right away
+        let mut mon = test_mon();
+        mon.cpu_history = history;
+        let _ = mon.update(Message::SetHistoryLen("10".into()));
+        assert_eq!(mon.cpu_history.len(), 10);
+        assert_eq!(mon.cpu_history.back(), Some(&99.0));
+    }
+
+    #[test]
+    fn test_selected_process_cpu_history() {
+        let mut mon = test_mon();
+        let me = sysinfo::get_current_pid().unwrap();
+        let _ = mon.update(Message::SelectProcess(me));
+        mon.record_selected_cpu();
+        mon.record_selected_cpu();
+        assert_eq!(mon.selected_cpu_history.len(), 2);
+        assert!(!mon.selected_exited);
+
+        // Selecting something else starts over
+        let other = *mon.sys.processes().keys().find(|pid| **pid != me).unwrap();
+        let _ = mon.update(Message::SelectProcess(other));
+        mon.record_selected_cpu();
+        assert_eq!(mon.selected_cpu_history.len(), 1);
+
+        // A vanished process freezes its history instead of clearing it
+        let gone = Pid::from_u32(u32::MAX);
+        mon.selected = Some(gone);
+        mon.selected_history_pid = Some(gone);
+        mon.record_selected_cpu();
+        assert!(mon.selected_exited);
+        assert_eq!(mon.selected_cpu_history.len(), 1);
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline([0.0, 50.0, 100.0].iter(), 10), "▁▅█");
+        assert_eq!(sparkline([0.0, 0.0, 100.0, 100.0].iter(), 2), "▁█");  // Averaged into buckets
+        assert_eq!(sparkline([].iter(), 10), "");
+    }
+
+    #[test]
+    fn test_text_bar_glyphs() {
+        assert_eq!(text_bar(50.0, "#", "-"), format!("[{}{}]", "#".repeat(10), "-".repeat(10)));
+        assert_eq!(text_bar(0.0, "█", "░"), format!("[{}]", "░".repeat(20)));
+        assert_eq!(text_bar(f64::NAN, "#", "-"), format!("[{}]", "-".repeat(20)));  // Garbage in, empty bar out
+    }
+
+    #[test]
+    fn test_format_process_cpu() {
+        assert_eq!(format_process_cpu(130.0, false), "130.0");
+        assert_eq!(format_process_cpu(130.0, true), "1.30");
+        assert_eq!(format_process_cpu(0.0, true), "0.00");
+        assert_eq!(process_cpu_label(true), "Cores");
+        assert_eq!(process_cpu_label(false), "CPU%");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0s");
+        assert_eq!(format_duration(59), "59s");
+        assert_eq!(format_duration(3_725), "1h 2m 5s");
+        assert_eq!(format_duration(90_061), "1d 1h 1m");
+    }
+
+    #[test]
+    fn test_truncate_end() {
+        assert_eq!(truncate_end("firefox", 8), "firefox");  // Shorter than the limit
+        assert_eq!(truncate_end("firefox", 7), "firefox");  // Exactly the limit
+        assert_eq!(truncate_end("firefox", 6), "firef…");   // One over
+        assert_eq!(truncate_end("firefox", 1), "…");
+        assert_eq!(truncate_end("firefox", 0), "");
+        assert_eq!(truncate_end("", 0), "");
+        assert_eq!(truncate_end("日本語のプロセス", 4), "日本語…");  // Counts characters, not bytes
+    }
+
+    #[test]
+    fn test_truncate_middle() {
+        assert_eq!(truncate_middle("processes.csv", 13), "processes.csv");
+        assert_eq!(truncate_middle("processes.csv", 12), "proces…s.csv");
+        assert_eq!(truncate_middle("processes.csv", 2), "p…");
+        assert_eq!(truncate_middle("processes.csv", 1), "…");
+        assert_eq!(truncate_middle("processes.csv", 0), "");
+        assert_eq!(truncate_middle("/home/me/exports/processes.csv", 16).chars().count(), 16);
+    }
+
+    #[test]
+    fn test_process_details_text() {
+        let mon = test_mon();
+        let me = sysinfo::get_current_pid().unwrap();
+        let details = mon.process_details_text(me).unwrap();
+        assert!(details.contains(&format!("PID: {}", me)));
+        assert!(details.contains("Executable:"));
+
+        assert!(mon.process_details_text(Pid::from(usize::MAX)).is_none());  // Unknown PID
+    }
+
+    #[test]
+    fn test_sort_disks() {
+        let disk = |mount: &str, used: u64| DiskInfo {
+            name: "dev".into(),
+            mount_point: mount.into(),
+            used,
+            total: 100,
+        };
+        let mut disks = vec![disk("/home", 90), disk("/", 20), disk("/data", 50)];
+
+        sort_disks(&mut disks, DiskSort::MountPoint, false);
+        let mounts: Vec<_> = disks.iter().map(|d| d.mount_point.as_str()).collect();
+        assert_eq!(mounts, ["/", "/data", "/home"]);
+
+        sort_disks(&mut disks, DiskSort::Usage, true);
+        assert_eq!(disks[0].mount_point, "/home");  // Fullest on top
+
+        assert_eq!(disk_totals(&disks), (160, 300));
+    }
+
+    #[test]
+    fn test_screen_enum_debug() {
+        let screen = Screen::Overview;
+        let _ = format!("{:?}", screen);  // Should not crash
+    }
+
+    #[test]
+    fn test_mem_percent_column_toggle() {
+        let mut mon = test_mon();
+        let _ = mon.update(Message::ToggleMemPercentColumn(true));
+        assert!(mon.current_config().show_mem_percent);
+        let _ = mon.update(Message::SortByMemoryPercent);
+        assert!(matches!(mon.sort_by, SortBy::MemoryPercent));
+        assert_eq!(usage_percent(512, 0), 0.0);  // Column is safe before memory_total is known
+    }
+
+    #[test]
+    fn test_label_color_follows_theme() {
+        // Labels get lighter on dark backgrounds and darker on light ones
+        assert!(label_color(true).r > panel_bg(true).r);
+        assert!(label_color(false).r < panel_bg(false).r);
+        assert_ne!(label_color(true), label_color(false));
+    }
+
+    #[test]
+    fn test_toast_colors_follow_theme() {
+        // Dark box with a lighter outline in dark mode, near-white box in light mode
+        assert!(toast_bg(true).r < toast_border(true).r);
+        assert!(toast_bg(false).r > toast_border(false).r);
+        assert!(toast_bg(true).r < toast_bg(false).r);
+    }
+
+    #[test]
+    fn test_new_process_highlight() {
+        let mut mon = test_mon();
+        mon.new_process_highlight_secs = 5;
+        let start = Instant::now();
+
+        // First tick only seeds the known set
+        mon.track_new_processes(start);
+        assert!(mon.new_pids.is_empty());
+
+        // Pretend one live process wasn't there last tick
+        let pid = *mon.sys.processes().keys().next().unwrap();
+        mon.known_pids.remove(&pid);
+        mon.track_new_processes(start);
+        assert!(mon.new_pids.contains_key(&pid));
+
+        // Still highlighted within the window, cleared after it
+        mon.track_new_processes(start + Duration::from_secs(2));
+        assert!(mon.new_pids.contains_key(&pid));
+        mon.track_new_processes(start + Duration::from_secs(6));
+        assert!(!mon.new_pids.contains_key(&pid));
+    }
+
+    #[test]
+    fn test_overview_metric_order_and_visibility() {
+        let mut mon = test_mon();
+        mon.overview_metrics = vec![OverviewMetric::Cpu, OverviewMetric::Memory, OverviewMetric::Disk];
+
+        let _ = mon.update(Message::ToggleOverviewMetric(OverviewMetric::Disk, false));
+        assert_eq!(mon.overview_metrics, vec![OverviewMetric::Cpu, OverviewMetric::Memory]);
+
+        let _ = mon.update(Message::ToggleOverviewMetric(OverviewMetric::Swap, true));
+        let _ = mon.update(Message::MoveOverviewMetricUp(OverviewMetric::Swap));
+        assert_eq!(mon.overview_metrics, vec![OverviewMetric::Cpu, OverviewMetric::Swap, OverviewMetric::Memory]);
+
+        // Moving past either end does nothing
+        let _ = mon.update(Message::MoveOverviewMetricUp(OverviewMetric::Cpu));
+        let _ = mon.update(Message::MoveOverviewMetricDown(OverviewMetric::Memory));
+        assert_eq!(mon.overview_metrics, vec![OverviewMetric::Cpu, OverviewMetric::Swap, OverviewMetric::Memory]);
+    }
+
+    #[test]
+    fn test_validate_config_rejects_duplicate_metrics() {
+        let config = AppConfig {
+            overview_metrics: vec![OverviewMetric::Cpu, OverviewMetric::Cpu],
+            ..AppConfig::default()
+        };
+        assert!(validate_config(&config).is_err());
+        assert!(validate_config(&AppConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn test_idle_threshold() {
+        assert!(!is_idle(0.0, 0.0));  // 0 turns dimming off
+        assert!(is_idle(0.2, 0.5));
+        assert!(!is_idle(0.5, 0.5));
+
+        let mut mon = test_mon();
+        let _ = mon.update(Message::SetIdleThreshold("1.5".to_string()));
+        assert_eq!(mon.idle_threshold, 1.5);
+        let _ = mon.update(Message::SetIdleThreshold("250".to_string()));
+        assert_eq!(mon.idle_threshold, 1.5);  // Out of range is ignored
+    }
+
+    #[test]
+    fn test_sortby_enum_debug() {
+        let sort = SortBy::Cpu;
+        let _ = format!("{:?}", sort);  // Should not crash
+    }
+
+    #[test]
+    fn test_message_enum_clone() {
+        let msg = Message::Tick;
+        let _cloned = msg.clone();  // Should be able to clone messages
+    }
+
+    #[test]
+    fn test_lightmon_get_processes_data() {
+        let mon = test_mon();
+        let data = mon.get_processes_data();
+        assert!(!data.is_empty());  // Should have some processes
+    }
+
+    #[test]
+    fn test_diagnostics_report_sections() {
+        let mon = test_mon();
+        let report = mon.build_diagnostics_report();
+        assert!(report.contains("[System]"));
+        assert!(report.contains("[Snapshot]"));
+        assert!(report.contains("refresh_interval"));  // Current config is included
+        assert!(report.contains("PID,Name,CPU%,Memory (KB),Status"));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_diagnostics_report_quotes_process_names() {
+        // A process whose name has a comma and a quote in it (names are cut at 15 bytes)
+        let dir = temp_path("bin");
+        fs::create_dir_all(&dir).unwrap();
+        let exe = dir.join("lm,\"x");
+        fs::copy("/bin/sleep", &exe).unwrap();
+        // Another test forking while the copy was open for writing makes exec fail
+        // with "text file busy" for a moment, so retry briefly
+        let mut child = (0..20)
+            .find_map(|_| {
+                let spawned = ProcessCommand::new(&exe).arg("30").spawn().ok();
+                if spawned.is_none() {
+                    std::thread::sleep(Duration::from_millis(50));
+                }
+                spawned
+            })
+            .unwrap();
+        let pid = Pid::from_u32(child.id());
+
+        let mut mon = test_mon();
+        let _ = mon.update(Message::ProcessTick);
+        finish_process_refresh(&mut mon);
+        let name = mon.sys.process(pid).map(|p| p.name().to_string());
+        let report = mon.build_diagnostics_report();
+        let _ = child.kill();
+        let _ = child.wait();
+        let _ = fs::remove_dir_all(&dir);
+
+        // The name is quoted, so the row still parses back as 5 columns
+        let name = name.unwrap();
+        assert!(name.contains(','));
+        let rows = &report[report.find(CSV_HEADER).unwrap()..];
+        let parsed = parse_processes_csv(rows).unwrap();
+        assert!(parsed.iter().any(|row| row.0 == pid && row.1 == name));
+    }
+
+    #[test]
+    fn test_set_refresh_interval_parsing() {
+        let mut mon = test_mon();
+
+        let _ = mon.update(Message::SetRefreshInterval("5".to_string()));
+        assert_eq!(mon.refresh_interval, 5);  // Should parse valid number
+
+        let _ = mon.update(Message::SetRefreshInterval("abc".to_string()));
+        assert_eq!(mon.refresh_interval, 5);  // Should keep previous value on invalid input
+    }
+
+    #[test]
+    fn test_parse_in_range() {
+        assert_eq!(parse_in_range(" 5 ", REFRESH_INTERVAL_RANGE), Ok(5));
+        assert_eq!(parse_in_range("0", REFRESH_INTERVAL_RANGE), Err("must be 1–3600".to_string()));
+        assert_eq!(parse_in_range("abc", REFRESH_INTERVAL_RANGE), Err("must be 1–3600".to_string()));
+        assert_eq!(parse_in_range("0", AUTO_EXPORT_RANGE), Ok(0));  // 0 = off is allowed here
+        assert!(parse_in_range("1.5", CPU_ALPHA_RANGE).is_err());
+
+        // Rust parses these as floats, but they're never usable settings
+        for input in ["NaN", "nan", "inf", "-inf", "infinity"] {
+            assert!(parse_in_range(input, CPU_ALPHA_RANGE).is_err(), "{} accepted", input);
+            assert!(parse_in_range(input, IDLE_THRESHOLD_RANGE).is_err(), "{} accepted", input);
+            assert!(parse_in_range(input, ALERT_THRESHOLD_RANGE).is_err(), "{} accepted", input);
+        }
+    }
+
+    #[test]
+    fn test_toggle_theme() {
+        let mut mon = test_mon();
+        let initial = mon.dark_mode;
+
+        let _ = mon.update(Message::ToggleTheme);
+        assert_ne!(mon.dark_mode, initial);  // Should flip the theme
+    }
+
+    #[test]
+    fn test_title_with_live_stats() {
+        let mut mon = test_mon();
+        assert_eq!(mon.title(), "System Monitor");
+
+        let _ = mon.update(Message::ToggleStatsInTitle(true));
+        mon.smooth_cpu = false;
+        mon.cpu_usage = 23.4;
+        mon.memory_used = 58;
+        mon.memory_total = 100;
+        assert_eq!(mon.title(), "LightMon — CPU 23% MEM 58%");
+    }
+
+    #[test]
+    fn test_toggle_status_bar() {
+        let mut mon = test_mon();
+        let _ = mon.update(Message::ToggleStatusBar(false));
+        assert!(!mon.show_status_bar);
+        assert!(!mon.current_config().show_status_bar);  // Persisted with the rest of the config
+    }
 
-    // Write each process as a row
-    for (pid, name, cpu_usage, memory, status) in processes {
-        let line = format!("{},{},{:.1},{},{}", pid, name, cpu_usage, memory, status);
-        writeln!(file, "{}", line)
-            .map_err(|e| format!("Can't write process data: {} - disk error", e))?;
+    #[test]
+    fn test_ctrl_d_toggles_theme() {
+        let ctrl_d = keyboard_shortcut(keyboard::Key::Character("d".into()), keyboard::Modifiers::CTRL);
+        assert!(matches!(ctrl_d, Some(Message::ToggleTheme)));
+
+        // Plain 'd' must not flip the theme
+        let plain_d = keyboard_shortcut(keyboard::Key::Character("d".into()), keyboard::Modifiers::empty());
+        assert!(plain_d.is_none());
     }
 
-    // Make sure everything is written to disk
-    file.flush()
-        .map_err(|e| format!("Can't save CSV file: {} - write failed", e))?;
+    #[test]
+    fn test_selected_pid_reuse_clears_selection() {
+        let mut mon = test_mon();
+        let me = sysinfo::get_current_pid().unwrap();
 
-    Ok(())
-}
+        let _ = mon.update(Message::SelectProcess(me));
+        mon.check_selected_pid_reuse();
+        assert_eq!(mon.selected, Some(me));  // Same process, nothing to do
 
-// Tests to make sure everything works
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
-    use std::path::PathBuf;
+        mon.selected_name = Some("definitely-not-this-process".into());
+        mon.check_selected_pid_reuse();
+        assert_eq!(mon.selected, None);
+        assert!(mon.toast_message.unwrap().contains("was reused"));
+    }
 
-    #[tokio::test]
-    async fn test_fetch_system_data_works() {
-        let result = fetch_system_data().await;
-        assert!(result.0 >= 0.0);  // CPU should be 0% or more
-        assert!(result.1 <= result.2);  // Used memory should be <= total memory
+    #[test]
+    fn test_narrow_breakpoint() {
+        assert!(is_narrow(320.0));
+        assert!(is_narrow(599.0));
+        assert!(!is_narrow(600.0));
+        assert!(!is_narrow(1024.0));
+
+        let mut mon = test_mon();
+        let _ = mon.update(Message::WindowResized(400.0, 700.0));
+        assert!(is_narrow(mon.window_width));  // Resize events drive the layout
     }
 
     #[test]
-    fn test_config_defaults() {
-        let config = AppConfig::default();
-        assert_eq!(config.refresh_interval, 1);
-        assert_eq!(config.dark_mode, false);
+    fn test_maximized_list() {
+        assert_eq!(visible_row_count(2000.0, false), DEFAULT_VISIBLE_ROWS);
+        assert!(visible_row_count(2000.0, true) > DEFAULT_VISIBLE_ROWS);
+        assert_eq!(visible_row_count(100.0, true), DEFAULT_VISIBLE_ROWS);  // Never fewer than normal
+
+        let mut mon = test_mon();
+        let _ = mon.update(Message::GoToProcesses);
+        let _ = mon.update(Message::ToggleMaximizeList);
+        assert!(mon.list_maximized_now());
+
+        // Escape restores the normal layout
+        let escape = keyboard_shortcut(keyboard::Key::Named(keyboard::key::Named::Escape), keyboard::Modifiers::empty());
+        let _ = mon.update(escape.unwrap());
+        assert!(!mon.list_maximized);
     }
 
     #[test]
-    fn test_load_config_no_crash() {
-        let config = load_config();
-        assert!(config.refresh_interval >= 1);  // Should have sensible value
+    fn test_settings_search() {
+        assert!(settings_section_matches("Theme", "dark light", ""));
+        assert!(settings_section_matches("Theme", "dark light", "  "));
+        assert!(settings_section_matches("Theme", "dark light", "DARK"));
+        assert!(settings_section_matches("Display", "status bar history", "status bar"));
+        assert!(!settings_section_matches("Display", "status bar history", "status csv"));
+        assert!(!settings_section_matches("Backup", "settings file", "theme"));
     }
 
     #[test]
-    fn test_screen_enum_debug() {
-        let screen = Screen::Overview;
-        format!("{:?}", screen);  // Should not crash
+    fn test_matches_filter() {
+        assert!(matches_filter("Firefox", Pid::from(42), "fire"));  // Case-insensitive name
+        assert!(matches_filter("bash", Pid::from(1234), "23"));  // PID substring
+        assert!(matches_filter("bash", Pid::from(1), ""));  // Empty filter shows all
+        assert!(!matches_filter("bash", Pid::from(1), "zsh"));
     }
 
     #[test]
-    fn test_sortby_enum_debug() {
-        let sort = SortBy::Cpu;
-        format!("{:?}", sort);  // Should not crash
+    fn test_row_click_actions() {
+        let mut mon = test_mon();
+        let me = sysinfo::get_current_pid().unwrap();
+
+        mon.row_click_action = RowClickAction::Select;
+        let _ = mon.update(Message::ProcessRowClicked(me));
+        assert_eq!(mon.selected, Some(me));
+
+        mon.selected = None;
+        mon.row_click_action = RowClickAction::Expand;
+        let _ = mon.update(Message::ProcessRowClicked(me));
+        assert_eq!(mon.expanded, Some(me));
+        assert_eq!(mon.selected, None);  // Expanding doesn't select
+        let _ = mon.update(Message::ProcessRowClicked(me));
+        assert_eq!(mon.expanded, None);  // Second click collapses
+
+        mon.row_click_action = RowClickAction::CopyPid;
+        let _ = mon.update(Message::ProcessRowClicked(me));
+        assert!(mon.toast_message.unwrap().contains(&me.to_string()));
     }
 
     #[test]
-    fn test_message_enum_clone() {
-        let msg = Message::Tick;
-        let _cloned = msg.clone();  // Should be able to clone messages
+    fn test_multi_selection() {
+        let mut mon = test_mon();
+        let me = sysinfo::get_current_pid().unwrap();
+        let other = *mon.sys.processes().keys().find(|pid| **pid != me).unwrap();
+
+        // Ctrl-click toggles membership instead of running the row action
+        let _ = mon.update(Message::ModifiersChanged(keyboard::Modifiers::CTRL));
+        let _ = mon.update(Message::ProcessRowClicked(me));
+        let _ = mon.update(Message::ProcessRowClicked(other));
+        assert_eq!(mon.multi_selected.len(), 2);
+        assert_eq!(mon.selected, None);
+        let _ = mon.update(Message::ProcessRowClicked(other));
+        assert_eq!(mon.multi_selected, HashSet::from([me]));
+
+        // Only the selected set goes out
+        let rows = mon.multi_selected_data();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].0, me);
+
+        let _ = mon.update(Message::ClearMultiSelection);
+        assert!(mon.multi_selected.is_empty());
+
+        // Plain clicks are back to normal once Ctrl is released
+        let _ = mon.update(Message::ModifiersChanged(keyboard::Modifiers::empty()));
+        mon.row_click_action = RowClickAction::Select;
+        let _ = mon.update(Message::ProcessRowClicked(me));
+        assert_eq!(mon.selected, Some(me));
     }
 
     #[test]
-    fn test_lightmon_get_processes_data() {
-        let mon = LightMon::new(()).0;
-        let data = mon.get_processes_data();
-        assert!(!data.is_empty());  // Should have some processes
+    fn test_tab_transition() {
+        let start = Instant::now();
+        assert_eq!(transition_offset(None, start), 0.0);
+        assert_eq!(transition_offset(Some(start), start), TRANSITION_DISTANCE);
+        let midway = transition_offset(Some(start), start + TRANSITION_DURATION / 2);
+        assert!(midway > 0.0 && midway < TRANSITION_DISTANCE);
+        assert_eq!(transition_offset(Some(start), start + TRANSITION_DURATION), 0.0);
+
+        let mut mon = test_mon();
+        mon.reduce_motion = Some(false);
+        let _ = mon.update(Message::GoToSettings);
+        assert!(mon.transition_started.is_some());
+        let _ = mon.update(Message::AnimationFrame(Instant::now() + TRANSITION_DURATION));
+        assert!(mon.transition_started.is_none());  // Finished, frames stop
+
+        // Reduce motion switches instantly
+        let _ = mon.update(Message::ToggleReduceMotion(true));
+        let _ = mon.update(Message::GoToOverview);
+        assert!(mon.transition_started.is_none());
     }
 
     #[test]
-    fn test_set_refresh_interval_parsing() {
-        let mut mon = LightMon::new(()).0;
+    fn test_unfocused_throttling() {
+        let mut mon = test_mon();
+        mon.refresh_interval = 2;
 
-        mon.update(Message::SetRefreshInterval("5".to_string()));
-        assert_eq!(mon.refresh_interval, 5);  // Should parse valid number
+        mon.throttle_when_unfocused = true;
+        let _ = mon.update(Message::WindowFocusChanged(false));
+        assert_eq!(mon.effective_refresh_interval(), 10);
+        let _ = mon.update(Message::WindowFocusChanged(true));
+        assert_eq!(mon.effective_refresh_interval(), 2);  // Back to normal on focus
 
-        mon.update(Message::SetRefreshInterval("abc".to_string()));
-        assert_eq!(mon.refresh_interval, 5);  // Should keep previous value on invalid input
+        mon.throttle_when_unfocused = false;
+        let _ = mon.update(Message::WindowFocusChanged(false));
+        assert_eq!(mon.effective_refresh_interval(), 2);  // Opted out
     }
 
     #[test]
-    fn test_toggle_theme() {
-        let mut mon = LightMon::new(()).0;
-        let initial = mon.dark_mode;
+    fn test_process_refresh_interval() {
+        let mut mon = test_mon();
+        mon.refresh_interval = 2;
+        mon.process_refresh_interval = 0;
+        assert_eq!(mon.effective_process_refresh_interval(), 2);  // Follows the main interval
 
-        mon.update(Message::ToggleTheme);
-        assert_ne!(mon.dark_mode, initial);  // Should flip the theme
+        let _ = mon.update(Message::SetProcessRefreshInterval("10".to_string()));
+        assert_eq!(mon.effective_process_refresh_interval(), 10);
+        let _ = mon.update(Message::SetProcessRefreshInterval("-1".to_string()));
+        assert_eq!(mon.process_refresh_interval, 10);  // Invalid input keeps the old value
+
+        // The overview tick no longer touches per-process state; the process tick does
+        let me = sysinfo::get_current_pid().unwrap();
+        mon.selected = Some(me);
+        let _ = mon.update(Message::Tick);
+        assert!(mon.selected_cpu_history.is_empty());
+        let _ = mon.update(Message::ProcessTick);
+        finish_process_refresh(&mut mon);
+        assert_eq!(mon.selected_cpu_history.len(), 1);
+    }
+
+    #[test]
+    fn test_auto_export_refreshes_derived_process_state() {
+        let mut mon = test_mon();
+        let me = sysinfo::get_current_pid().unwrap();
+        mon.selected = Some(me);
+        assert!(mon.known_pids.is_empty());
+
+        // Off the Processes tab, the auto-export refresh still feeds new-process
+        // tracking and the selected process's history
+        let _ = mon.update(Message::AutoExportTick);
+        assert!(mon.after_process_refresh.iter().any(|m| matches!(m, Message::WriteAutoExport)));
+        finish_process_refresh(&mut mon);
+        assert!(mon.after_process_refresh.is_empty());  // Export was handed off
+        assert!(mon.known_pids.contains(&me));
+        assert_eq!(mon.selected_cpu_history.len(), 1);
+    }
+
+    #[test]
+    fn test_never_kills_self() {
+        let mut mon = test_mon();
+        let me = sysinfo::get_current_pid().unwrap();
+        assert!(mon.kill_process(me).is_err());
+
+        // Holds even when selected and the hide toggle is off
+        mon.hide_self = false;
+        let _ = mon.update(Message::SelectProcess(me));
+        let _ = mon.update(Message::KillProcess);
+        assert_eq!(mon.selected, Some(me));
+        assert!(mon.toast_message.unwrap().contains('❌'));
+    }
+
+    #[test]
+    fn test_critical_process_needs_confirmation() {
+        let mut mon = test_mon();
+        assert!(mon.is_critical_process("SYSTEMD"));  // Case-insensitive
+        assert!(!mon.is_critical_process("firefox"));
+
+        // Mark our own process critical; kill_process refuses self anyway, so this is safe
+        let me = sysinfo::get_current_pid().unwrap();
+        let my_name = mon.sys.process(me).unwrap().name().to_string();
+        let _ = mon.update(Message::CriticalNameInputChanged(my_name.clone()));
+        let _ = mon.update(Message::AddCriticalProcess);
+        assert!(mon.current_config().critical_processes.contains(&my_name));
+
+        let _ = mon.update(Message::SelectProcess(me));
+        let _ = mon.update(Message::KillProcess);
+        assert_eq!(mon.pending_critical_kill, Some(me));  // First press only asks
+        assert!(mon.toast_message.take().unwrap().contains("critical"));
+
+        let _ = mon.update(Message::CancelKill);
+        assert_eq!(mon.pending_critical_kill, None);
+
+        let _ = mon.update(Message::KillProcess);
+        let _ = mon.update(Message::KillProcess);  // Confirmed - reaches kill_process
+        assert_eq!(mon.pending_critical_kill, None);
+        assert!(mon.toast_message.take().unwrap().contains("LightMon itself"));
+
+        let _ = mon.update(Message::RemoveCriticalProcess(my_name.clone()));
+        assert!(!mon.is_critical_process(&my_name));
+    }
+
+    #[test]
+    fn test_hide_self_toggle() {
+        let mut mon = test_mon();
+        let _ = mon.update(Message::ToggleHideSelf(true));
+        assert!(mon.current_config().hide_self);
+        assert_eq!(mon.self_pid, sysinfo::get_current_pid().ok());
     }
 
     #[test]
     fn test_filter_changed() {
-        let mut mon = LightMon::new(()).0;
-        mon.update(Message::FilterChanged("test".to_string()));
+        let mut mon = test_mon();
+        let _ = mon.update(Message::FilterChanged("test".to_string()));
         assert_eq!(mon.filter_text, "test");  // Should update filter text
     }
 
+    #[test]
+    fn test_export_flow_toggles_exporting_state() {
+        let mut mon = test_mon();
+        assert!(!mon.is_exporting);
+
+        drive(&mut mon, [Message::ExportProcesses]);
+        assert!(mon.is_exporting);  // Button shows "Exporting..." while the task runs
+        assert!(mon.toast_message.is_none());
+
+        drive(&mut mon, [Message::ExportComplete(Ok(PathBuf::from("processes.csv")))]);
+        assert!(!mon.is_exporting);
+        assert!(mon.toast_message.as_deref().unwrap().starts_with('✅'));
+
+        drive(&mut mon, [Message::ToastTick(Instant::now() + TOAST_DURATION)]);
+        assert!(mon.toast_message.is_none());
+    }
+
+    #[test]
+    fn test_old_toast_expiry_keeps_newer_toast() {
+        let mut mon = test_mon();
+        let start = Instant::now();
+        mon.show_toast("first".into());
+        mon.toast_shown_at = Some(start - Duration::from_secs(2));  // Shown 2s ago
+
+        // A newer toast replaces it before the first one's 3s are up
+        drive(&mut mon, [Message::ExportComplete(Err("disk full".into()))]);
+        let newer = mon.toast_message.clone();
+
+        // The first toast's deadline passes, but the newer one has its own clock
+        drive(&mut mon, [Message::ToastTick(start + Duration::from_secs(2))]);
+        assert_eq!(mon.toast_message, newer);
+
+        drive(&mut mon, [Message::ToastTick(Instant::now() + TOAST_DURATION)]);
+        assert!(mon.toast_message.is_none());
+    }
+
+    #[test]
+    fn test_export_failure_shows_error_toast() {
+        let mut mon = test_mon();
+        drive(&mut mon, [
+            Message::ExportProcesses,
+            Message::ExportComplete(Err("disk full".into())),
+        ]);
+
+        assert!(!mon.is_exporting);
+        let toast = mon.toast_message.unwrap();
+        assert!(toast.contains('❌'));  // Error styling keys off this
+        assert!(toast.contains("disk full"));
+    }
+
+    #[test]
+    fn test_selection_flow() {
+        let mut mon = test_mon();
+        let me = sysinfo::get_current_pid().unwrap();
+        let other = *mon.sys.processes().keys().find(|pid| **pid != me).unwrap();
+
+        drive(&mut mon, [Message::GoToProcesses, Message::SelectProcess(me)]);
+        assert_eq!(mon.selected, Some(me));
+        assert_eq!(mon.selected_name.as_deref(), mon.sys.process(me).map(|p| p.name()));
+
+        // Switching tabs keeps the selection; picking another row replaces it
+        drive(&mut mon, [Message::GoToOverview, Message::GoToProcesses, Message::SelectProcess(other)]);
+        assert_eq!(mon.selected, Some(other));
+    }
+
+    #[test]
+    fn test_kill_without_selection_warns() {
+        let mut mon = test_mon();
+        drive(&mut mon, [Message::KillProcess]);
+
+        assert_eq!(mon.selected, None);
+        assert!(mon.toast_message.unwrap().starts_with("⚠️"));
+    }
+
     #[test]
     fn test_kill_process_function_exists() {
-        let mon = LightMon::new(()).0;
+        let mon = test_mon();
         let result = mon.kill_process(Pid::from(99999));  // Invalid PID
         assert!(result.is_err());  // Should fail gracefully
     }
@@ -847,45 +5469,512 @@ mod tests {
         let test_config = AppConfig {
             refresh_interval: 3,
             dark_mode: true,
+            ..AppConfig::default()
         };
 
-        let result = save_config(&test_config);
+        let config_path = temp_path("config.toml");
+        let result = save_config(&config_path, &test_config);
         assert!(result.is_ok());
-        assert!(PathBuf::from("lightmon_config.toml").exists());
+        assert!(config_path.exists());
 
         // Clean up test file
-        let _ = fs::remove_file("lightmon_config.toml");
+        let _ = fs::remove_file(&config_path);
     }
 
     #[test]
     fn test_config_round_trip() {
-        let original_config = load_config();
-
         let test_config = AppConfig {
             refresh_interval: 7,
             dark_mode: false,
+            ..AppConfig::default()
         };
 
-        save_config(&test_config).unwrap();
-
-        let config_path = PathBuf::from("lightmon_config.toml");
-        let config_str = fs::read_to_string(&config_path).unwrap();
-        let loaded_config: AppConfig = toml::from_str(&config_str).unwrap();
+        let config_path = temp_path("config.toml");
+        save_config(&config_path, &test_config).unwrap();
+        let loaded_config = load_config(&config_path);
 
         assert_eq!(loaded_config.refresh_interval, 7);
-        assert_eq!(loaded_config.dark_mode, false);
+        assert!(!loaded_config.dark_mode);
+
+        let _ = fs::remove_file(&config_path);
+    }
+
+    #[test]
+    fn test_settings_changes_go_to_injected_config_path() {
+        let mut mon = test_mon();
+        let _ = mon.update(Message::ToggleStatsInTitle(true));
+        assert!(load_config(&mon.config_path).stats_in_title);
+
+        // A new instance on the same file picks the change up; another one doesn't
+        let config_path = mon.config_path.clone();
+        let reloaded = LightMon::with_config(load_config(&config_path), config_path.clone()).0;
+        assert!(reloaded.stats_in_title);
+        assert!(!test_mon().stats_in_title);
+
+        let _ = fs::remove_file(&config_path);
+    }
+
+    #[test]
+    fn test_failed_settings_save_is_reported() {
+        // A directory where the config file should be makes every save fail
+        let config_path = temp_path("config_dir");
+        fs::create_dir_all(&config_path).unwrap();
+        let mut mon = LightMon::with_config(AppConfig::default(), config_path.clone()).0;
+
+        // Numeric setters report it too, not just the toggles
+        let _ = mon.update(Message::SetRefreshInterval("5".to_string()));
+        assert!(mon.toast_message.take().is_some_and(|t| t.starts_with("❌ Couldn't save settings")));
+        let _ = mon.update(Message::ToggleStatsInTitle(true));
+        assert!(mon.toast_message.take().is_some_and(|t| t.starts_with("❌ Couldn't save settings")));
+
+        let _ = fs::remove_dir_all(&config_path);
+    }
+
+    #[tokio::test]
+    async fn test_settings_export_import_round_trip() {
+        let path = temp_path("settings.toml");
+        let config = AppConfig {
+            refresh_interval: 9,
+            bar_style: BarStyle::Ascii,
+            ..AppConfig::default()
+        };
+
+        export_settings(path.clone(), config).await.unwrap();
+        let imported = import_settings(path.clone()).await.unwrap();
+        assert_eq!(imported.refresh_interval, 9);
+        assert_eq!(imported.bar_style, BarStyle::Ascii);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_import_settings_rejects_invalid_files() {
+        let path = temp_path("bad_settings.toml");
+
+        fs::write(&path, "refresh_interval = \"soon\"").unwrap();
+        assert!(import_settings(path.clone()).await.is_err());  // Doesn't parse
 
-        // Restore original config
-        save_config(&original_config).unwrap();
+        fs::write(&path, "refresh_interval = 0").unwrap();
+        assert!(import_settings(path.clone()).await.is_err());  // Parses but out of range
+
+        fs::write(&path, "cpu_smoothing_alpha = nan").unwrap();
+        assert_eq!(
+            import_settings(path.clone()).await.map(|_| ()),
+            Err("cpu_smoothing_alpha must be 0.01–1".to_string()),
+        );
+
+        let _ = fs::remove_file(&path);
     }
 
     #[tokio::test]
     async fn test_export_processes_to_csv_success() {
         let processes = vec![(Pid::from(1), "test.exe".into(), 0.0, 1024, "Running".into())];
-        let result = export_processes_to_csv(processes).await;
-        assert!(result.is_ok());
+        let path = temp_path("processes.csv");
+        let result = export_processes_to_csv(path.clone(), processes).await;
+        assert_eq!(result, Ok(path.clone()));
 
         // Clean up test file
-        let _ = fs::remove_file("processes.csv");
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_parse_processes_csv() {
+        let csv = "PID,Name,CPU%,Memory (KB),Status\r\n1,init,0.0,2048,Sleep\r\n\r\n42,\"my,\"\"app\"\"\",12.5,4096,Run\r\n";
+        let rows = parse_processes_csv(csv).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1].0, Pid::from(42));
+        assert_eq!(rows[1].1, "my,\"app\"");  // Quoted commas and doubled quotes
+        assert_eq!(rows[1].3, 4096);
+
+        assert!(parse_processes_csv("not,a,csv").is_err());
+        assert!(parse_processes_csv("PID,Name,CPU%,Memory (KB),Status\nx,init,0.0,1,Run").is_err());
+        // An unquoted comma is a sixth column, not part of the name
+        assert_eq!(
+            parse_processes_csv("PID,Name,CPU%,Memory (KB),Status\n1,my,app,0.0,1,Run"),
+            Err("line 2: expected 5 columns".to_string()),
+        );
+        assert_eq!(
+            parse_processes_csv("PID,Name,CPU%,Memory (KB),Status\n1,\"open,0.0,1,Run\n"),
+            Err("line 2: unterminated quoted field".to_string()),
+        );
+    }
+
+    #[test]
+    fn test_parse_processes_csv_round_trip() {
+        let path = temp_path("roundtrip.csv");
+        let processes = vec![
+            (Pid::from(7), "worker".to_string(), 3.5, 1000, "Run".to_string()),
+            (Pid::from(8), "a, b".to_string(), 0.0, 1, "Sleep".to_string()),
+            (Pid::from(9), "say \"hi\"".to_string(), 0.0, 1, "Run".to_string()),
+            (Pid::from(10), "two\nlines\r".to_string(), 0.0, 1, "Run".to_string()),
+        ];
+        write_processes_csv(&path, &processes).unwrap();
+
+        let parsed = parse_processes_csv(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(parsed, processes);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_non_utf8_process_name_in_csv() {
+        let name = sanitize_process_name(b"bad\xff\xfe,name");
+        assert_eq!(name, "bad\u{FFFD}\u{FFFD},name");
+
+        let path = temp_path("utf8.csv");
+        let processes = vec![(Pid::from(9), name, 0.0, 1, "Run".to_string())];
+        write_processes_csv(&path, &processes).unwrap();
+
+        // Reads back as valid UTF-8, quoted, with the row intact
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("9,\"bad\u{FFFD}\u{FFFD},name\",0.0,1,Run"));
+        assert_eq!(parse_processes_csv(&contents).unwrap(), processes);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_live_process_export_round_trips() {
+        let mon = test_mon();
+        let path = temp_path("live.csv");
+        let exported = mon.get_processes_data();
+        assert!(!exported.is_empty());
+        write_processes_csv(&path, &exported).unwrap();
+
+        let parsed = parse_processes_csv(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(parsed.len(), exported.len());
+        for (read, written) in parsed.iter().zip(&exported) {
+            assert_eq!((read.0, &read.1, read.3, &read.4), (written.0, &written.1, written.3, &written.4));
+            assert!((read.2 - written.2).abs() <= 0.05);  // CPU% is written to one decimal
+        }
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let row = |pid: usize, name: &str, cpu: f32, mem: u64| (Pid::from(pid), name.to_string(), cpu, mem, "Run".to_string());
+        let before = vec![row(1, "steady", 1.0, 1000), row(2, "spiky", 1.0, 1000), row(3, "gone", 0.0, 10), row(4, "old", 0.0, 10)];
+        let after = vec![row(1, "steady", 2.0, 1100), row(2, "spiky", 40.0, 1000), row(5, "new", 0.0, 10), row(4, "reused", 0.0, 10)];
+
+        let diff = diff_snapshots(&before, &after);
+        let summary: Vec<_> = diff.iter().map(|d| (d.kind, d.name.as_str())).collect();
+        assert_eq!(summary, vec![
+            (DiffKind::Appeared, "reused"),
+            (DiffKind::Appeared, "new"),
+            (DiffKind::Disappeared, "gone"),
+            (DiffKind::Disappeared, "old"),
+            (DiffKind::Changed, "spiky"),
+        ]);
+    }
+
+    #[test]
+    fn test_renice_validates_input() {
+        let mut mon = test_mon();
+        let _ = mon.update(Message::ReniceProcess);
+        assert!(mon.toast_message.take().unwrap().starts_with("⚠️"));  // Nothing selected
+
+        let me = sysinfo::get_current_pid().unwrap();
+        let _ = mon.update(Message::SelectProcess(me));
+        let _ = mon.update(Message::NiceInputChanged("42".into()));
+        let _ = mon.update(Message::ReniceProcess);
+        assert!(mon.toast_message.unwrap().contains("must be -20–19"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_process_nice_reads_own_process() {
+        let nice = process_nice(sysinfo::get_current_pid().unwrap()).unwrap();
+        assert!((NICE_RANGE.0..=NICE_RANGE.1).contains(&nice));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_open_handle_count_reads_own_process() {
+        // At least stdin/stdout/stderr
+        assert!(open_handle_count(sysinfo::get_current_pid().unwrap()).unwrap() >= 3);
+    }
+
+    #[test]
+    fn test_open_handles_ignores_stale_selection() {
+        let mut mon = test_mon();
+        let me = sysinfo::get_current_pid().unwrap();
+        let _ = mon.update(Message::SelectProcess(me));
+
+        let info = SelectedProcessInfo { nice: Some(5), open_handles: Some(7), shared_memory: Some(4096) };
+        let _ = mon.update(Message::SelectedInfo(Pid::from(u32::MAX as usize), info));
+        assert_eq!(mon.selected_info, None);  // Answer for another process
+
+        let _ = mon.update(Message::SelectedInfo(me, info));
+        assert_eq!(mon.selected_info, Some((me, info)));
+        assert_eq!(mon.nice_input, "5");  // First reading prefills the editor
+
+        // Later readings don't overwrite what the user is typing
+        mon.nice_input = "10".into();
+        let _ = mon.update(Message::SelectedInfo(me, SelectedProcessInfo { nice: Some(0), ..info }));
+        assert_eq!(mon.nice_input, "10");
+    }
+
+    #[test]
+    fn test_export_start_dir_fallback() {
+        assert_eq!(export_start_dir(None), None);  // Nothing remembered - current folder
+
+        let existing = std::env::temp_dir();
+        assert_eq!(export_start_dir(Some(&existing)), Some(existing.clone()));
+
+        // A remembered folder that was deleted falls back to home
+        let gone = existing.join(format!("lightmon_gone_{}", std::process::id()));
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")).map(PathBuf::from);
+        assert_eq!(export_start_dir(Some(&gone)), home);
+    }
+
+    #[test]
+    fn test_export_remembers_folder() {
+        let mut mon = test_mon();
+        let dir = std::env::temp_dir();
+        let _ = mon.update(Message::ExportDirChanged(dir.display().to_string()));
+        assert_eq!(mon.export_path("processes.csv"), dir.join("processes.csv"));
+
+        let _ = mon.update(Message::ExportComplete(Ok(dir.join("processes.csv"))));
+        assert_eq!(mon.current_config().last_export_dir, Some(dir));
+
+        // Exports to the current folder forget the remembered one
+        let _ = mon.update(Message::ExportComplete(Ok(PathBuf::from("processes.csv"))));
+        assert_eq!(mon.current_config().last_export_dir, None);
+    }
+
+    #[test]
+    fn test_group_by_parent() {
+        let pid = Pid::from;
+        let processes = vec![
+            (pid(1), None, 0.5, 100),           // Parent with its own usage
+            (pid(10), Some(pid(1)), 10.0, 1000),
+            (pid(11), Some(pid(1)), 5.0, 2000),
+            (pid(20), Some(pid(99)), 50.0, 10),  // Parent already exited
+            (pid(30), None, 80.0, 10),           // No parent, no children - not a group
+        ];
+        let groups = group_by_parent(&processes);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0], ParentGroup { parent: pid(99), children: vec![pid(20)], cpu: 50.0, memory: 10 });
+        assert_eq!(groups[1], ParentGroup { parent: pid(1), children: vec![pid(10), pid(11)], cpu: 15.5, memory: 3100 });
+    }
+
+    #[test]
+    fn test_auto_export_path_is_timestamped() {
+        let t = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        assert_eq!(auto_export_path(t), PathBuf::from("processes_1700000000.csv"));
+    }
+
+    #[test]
+    fn test_set_auto_export_interval() {
+        let mut mon = test_mon();
+        let _ = mon.update(Message::SetAutoExportInterval("15".to_string()));
+        assert_eq!(mon.auto_export_minutes, 15);
+
+        let _ = mon.update(Message::SetAutoExportInterval("0".to_string()));
+        assert_eq!(mon.auto_export_minutes, 0);  // 0 disables
+    }
+
+    #[test]
+    fn test_write_processes_csv_cleans_up_temp_on_failure() {
+        // A directory sitting at the target path makes the final rename fail
+        let dir = std::env::temp_dir().join(format!("lightmon_export_test_{}", std::process::id()));
+        let target = dir.join("processes.csv");
+        fs::create_dir_all(&target).unwrap();
+
+        let processes = vec![(Pid::from(1), "test.exe".into(), 0.0, 1024, "Running".into())];
+        let result = write_processes_csv(&target, &processes);
+
+        assert!(result.is_err());
+        assert!(!dir.join("processes.csv.tmp").exists());  // Temp file removed
+        assert!(target.is_dir());  // Existing target left alone
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_parse_renderer_flag() {
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(parse_renderer_flag(args(&[])), Ok(None));
+        assert_eq!(parse_renderer_flag(args(&["--renderer", "tiny-skia"])), Ok(Some(RendererChoice::TinySkia)));
+        assert_eq!(parse_renderer_flag(args(&["--renderer=WGPU"])), Ok(Some(RendererChoice::Wgpu)));
+        assert_eq!(parse_renderer_flag(args(&["--other", "--renderer", "auto"])), Ok(Some(RendererChoice::Auto)));
+        assert!(parse_renderer_flag(args(&["--renderer", "vulkan"])).is_err());
+        assert!(parse_renderer_flag(args(&["--renderer"])).is_err());
+
+        // Every choice hands iced a list it accepts
+        for choice in RendererChoice::ALL {
+            assert!(is_valid_iced_backend(choice.backends()));
+        }
+    }
+
+    #[test]
+    fn test_is_valid_iced_backend() {
+        assert!(is_valid_iced_backend("wgpu"));
+        assert!(is_valid_iced_backend("tiny-skia, wgpu"));
+        assert!(!is_valid_iced_backend("vulkan"));
+        assert!(!is_valid_iced_backend("wgpu,"));
+        assert!(!is_valid_iced_backend(""));
+    }
+
+    #[test]
+    fn test_relaunch_args() {
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            relaunch_args(args(&["--renderer", "wgpu", "--other", "--renderer=tiny-skia", "--wait-for-pid", "12", "x"])),
+            args(&["--other", "x"]),
+        );
+        assert!(relaunch_args(args(&["--renderer"])).is_empty());
+    }
+
+    #[test]
+    fn test_wait_for_pid_handoff() {
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert_eq!(parse_wait_for_pid(args(&["--other", "--wait-for-pid", "42"])), Some(Pid::from(42)));
+        assert_eq!(parse_wait_for_pid(args(&["--wait-for-pid", "nope"])), None);
+        assert_eq!(parse_wait_for_pid(args(&["--wait-for-pid"])), None);
+        assert_eq!(parse_wait_for_pid(args(&[])), None);
+
+        // Returns straight away once the process is gone...
+        let start = Instant::now();
+        wait_for_exit(Pid::from(u32::MAX as usize), Duration::from_secs(5));
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        // ...and gives up on one that's still running
+        let me = sysinfo::get_current_pid().unwrap();
+        let start = Instant::now();
+        wait_for_exit(me, Duration::from_millis(100));
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_renderer_change_requires_restart() {
+        let mut mon = test_mon();
+        assert!(mon.restart_required().is_empty());
+
+        let other = if mon.startup_renderer == RendererChoice::TinySkia { RendererChoice::Wgpu } else { RendererChoice::TinySkia };
+        let _ = mon.update(Message::SetRenderer(other));
+        assert_eq!(mon.restart_required(), vec!["Renderer"]);
+        assert_eq!(mon.current_config().renderer, other);  // Saved for the next launch
+
+        let startup = mon.startup_renderer;
+        let _ = mon.update(Message::SetRenderer(startup));
+        assert!(mon.restart_required().is_empty());  // Changed back - nothing pending
+    }
+
+    #[test]
+    fn test_whats_new_shown_once_after_upgrade() {
+        // Fresh installs have nothing new to announce
+        assert!(whats_new(AppConfig::default().last_seen_version.as_deref()).is_none());
+
+        // A config saved by an older version has no last_seen_version
+        let upgraded: AppConfig = toml::from_str("refresh_interval = 2").unwrap();
+        assert!(upgraded.last_seen_version.is_none());
+        assert!(whats_new(None).is_some());
+        assert!(whats_new(Some("v0.9")).is_some());
+
+        let mut mon = test_mon();
+        mon.last_seen_version = None;
+        let _ = mon.update(Message::DismissWhatsNew);
+        assert_eq!(mon.current_config().last_seen_version.as_deref(), Some(BETA_TAG));
+        assert!(whats_new(mon.last_seen_version.as_deref()).is_none());
+    }
+
+    #[test]
+    fn test_overview_layout_persists() {
+        assert_eq!(AppConfig::default().overview_layout, OverviewLayout::Stacked);
+
+        let mut mon = test_mon();
+        let _ = mon.update(Message::SetOverviewLayout(OverviewLayout::Grid));
+        let saved = toml::to_string(&mon.current_config()).unwrap();
+        let loaded: AppConfig = toml::from_str(&saved).unwrap();
+        assert_eq!(loaded.overview_layout, OverviewLayout::Grid);
+    }
+
+    #[test]
+    fn test_alert_crossed_fires_once_per_breach() {
+        let mut active = false;
+        assert!(!alert_crossed(&mut active, 50.0, 90.0));
+        assert!(alert_crossed(&mut active, 95.0, 90.0));   // Crossed
+        assert!(!alert_crossed(&mut active, 99.0, 90.0));  // Still over - no repeat
+        assert!(!alert_crossed(&mut active, 80.0, 90.0));  // Back under re-arms
+        assert!(alert_crossed(&mut active, 90.0, 90.0));   // Reaching it counts
+        assert!(!alert_crossed(&mut active, 100.0, 0.0));  // 0 = off
+        assert!(!active);
+    }
+
+    #[test]
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    fn test_notification_command_passes_text_verbatim() {
+        let title = "-t \"LightMon\"";
+        let body = "CPU at 95%; $(reboot) 'quoted'";
+        let command = notification_command(title, body);
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, ["--app-name=LightMon", "--", title, body]);
+    }
+
+    #[test]
+    fn test_cpu_alert_toast() {
+        let mut mon = test_mon();
+        let _ = mon.update(Message::SetCpuAlertThreshold("50".to_string()));
+
+        let _ = mon.update(Message::SystemData(80.0, 1, 100, Vec::new()));
+        assert!(mon.toast_message.take().unwrap().contains("CPU at 80%"));
+        let _ = mon.update(Message::SystemData(85.0, 1, 100, Vec::new()));
+        assert!(mon.toast_message.is_none());  // Same breach, no second alert
+    }
+
+    #[tokio::test]
+    async fn test_process_refresh_runs_in_background_and_swaps_in() {
+        let mut mon = test_mon();
+        let me = sysinfo::get_current_pid().unwrap();
+
+        let _ = mon.update(Message::ProcessTick);
+        assert!(mon.process_refresh_in_flight);
+        let _ = mon.update(Message::ProcessTick);  // No second refresh while one is running
+
+        refresh_processes_in_background(Arc::clone(&mon.spare_sys)).await;
+        let _ = mon.update(Message::ProcessesRefreshed);
+        assert!(!mon.process_refresh_in_flight);
+        assert!(mon.sys.process(me).is_some());
+        assert!(lock_system(&mon.spare_sys).process(me).is_some());  // Old one kept for next time
+    }
+
+    // Process CPU% must match what a single System refreshed on the same schedule
+    // reports, whichever process System is on screen and with ticks landing in between
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_process_cpu_consistent_across_swaps() {
+        // Busy part of the time, idle the rest, so the reading isn't capped at 100%
+        let script = "while :; do i=0; while [ $i -lt 20000 ]; do i=$((i+1)); done; sleep 0.1; done";
+        let mut child = ProcessCommand::new("sh").args(["-c", script]).spawn().unwrap();
+        let pid = Pid::from_u32(child.id());
+        let mut mon = test_mon();
+        let mut reference = System::new_all();
+
+        let mut readings = Vec::new();
+        for _ in 0..6 {
+            // Slower process refreshes than ticks, like a non-zero process_refresh_interval.
+            // Ticks are > 200ms apart, or sysinfo ignores them
+            for _ in 0..2 {
+                std::thread::sleep(Duration::from_millis(250));
+                let _ = mon.update(Message::Tick);
+            }
+            std::thread::sleep(Duration::from_millis(250));
+            let _ = mon.update(Message::ProcessTick);
+            finish_process_refresh(&mut mon);
+            reference.refresh_processes();
+            let cpu = |sys: &System| sys.process(pid).map_or(0.0, |p| p.cpu_usage());
+            readings.push((cpu(&mon.sys), cpu(&reference)));
+        }
+        let _ = child.kill();
+        let _ = child.wait();
+
+        // Each shown System spans the last two refreshes, so compare against the
+        // busier of the matching reference readings. Skip the warm-up rounds
+        for window in readings[2..].windows(2) {
+            let (shown, _) = window[1];
+            let expected = window[0].1.max(window[1].1);
+            assert!(shown <= expected * 1.5 + 5.0, "shown {:.0}% vs reference {:.0}%: {:?}", shown, expected, readings);
+        }
     }
-}
\ No newline at end of file
+}